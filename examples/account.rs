@@ -0,0 +1,26 @@
+//! Run with
+//!
+//! ```not_rust
+//! API_KEY="xxxxxxxx" API_SECRET="xxxxxxxx" cargo run --example account
+//! ```
+
+use binance::spot::{BASE_URL_API, Client, ClientConfig, GetAccountInformationParams};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cfg = ClientConfig {
+        base_url: BASE_URL_API.to_string(),
+        api_key: Some(std::env::var("API_KEY")?.into()),
+        api_secret: Some(std::env::var("API_SECRET")?.into()),
+        ..Default::default()
+    };
+    let client = Client::new(cfg)?;
+
+    let params = GetAccountInformationParams {
+        omit_zero_balances: Some(true),
+    };
+    let response = client.get_account_information(params).await?;
+    println!("{response:#?}");
+
+    Ok(())
+}
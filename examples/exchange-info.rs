@@ -4,18 +4,15 @@
 //! cargo run --example exchange-info
 //! ```
 
-use tokio;
-
 use binance::spot::{BASE_URL_API, Client, ClientConfig, GetExchangeInfoParams};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cfg = ClientConfig {
         base_url: BASE_URL_API.to_string(),
-        api_key: None,
-        api_secret: None,
+        ..Default::default()
     };
-    let client = Client::new(cfg);
+    let client = Client::new(cfg)?;
 
     let params = GetExchangeInfoParams {
         symbol: Some(String::from("BTCUSDT")),
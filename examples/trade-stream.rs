@@ -0,0 +1,31 @@
+//! Run with
+//!
+//! ```not_rust
+//! cargo run --example trade-stream
+//! ```
+
+use binance::spot::stream::{Stream, StreamClient, StreamConfig};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let client = StreamClient::new(StreamConfig::default());
+
+    let streams = [
+        Stream::Trade {
+            symbol: String::from("BTCUSDT"),
+        },
+        Stream::BookTicker {
+            symbol: String::from("BTCUSDT"),
+        },
+    ];
+    let mut connection = client.connect(&streams).await?;
+    for _ in 0..10 {
+        let Some(event) = connection.next().await else {
+            break;
+        };
+        println!("{:#?}", event?);
+    }
+    connection.close().await?;
+
+    Ok(())
+}
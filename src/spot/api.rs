@@ -1,8 +1,14 @@
+use std::collections::BTreeMap;
+
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use crate::spot::{
-    ExchangeFilter, KlineInterval, OrderType, RateLimitInterval, RateLimiter, STPMode, SymbolStatus,
+    AllocationType, ApiError, CancelReplaceMode, CancelReplaceStatus, CancelRestrictions,
+    ContingencyType, ExchangeFilter, KlineInterval, MatchType, OrderListOrderStatus,
+    OrderListStatus, OrderRateLimitExceededMode, OrderResponseType, OrderSide, OrderStatus,
+    OrderType, RateLimitInterval, RateLimitWindow, RateLimiter, STPMode, SymbolStatus, TimeInForce,
+    WorkingFloor,
 };
 
 pub type Timestamp = u64;
@@ -11,11 +17,18 @@ pub type Timestamp = u64;
 pub struct Response<T> {
     pub result: T,
     pub headers: Headers,
+    /// Number of HTTP attempts made, more than 1 if the request was retried.
+    pub attempts: u32,
 }
 
 #[derive(Debug, PartialEq)]
 pub struct Headers {
     pub retry_after: Option<Timestamp>,
+    /// X-MBX-USED-WEIGHT-(intervalNum)(intervalLetter): request weight used by the IP.
+    pub used_weight: BTreeMap<RateLimitWindow, u64>,
+    /// X-MBX-ORDER-COUNT-(intervalNum)(intervalLetter): orders placed by the account.
+    /// Present only on order placement responses.
+    pub order_count: BTreeMap<RateLimitWindow, u64>,
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
@@ -35,14 +48,12 @@ pub struct GetExchangeInfoParams {
     /// Examples: curl -X GET "https://api.binance.com/api/v3/exchangeInfo?symbols=%5B%22BNBBTC%22,%22BTCUSDT%22%5D"
     /// or
     /// curl -g -X GET 'https://api.binance.com/api/v3/exchangeInfo?symbols=["BTCUSDT","BNBBTC"]'
-    /// TODO: Check serialization.
     pub symbols: Option<Vec<String>>,
     /// Examples: curl -X GET "https://api.binance.com/api/v3/exchangeInfo?permissions=SPOT"
     /// or
     /// curl -X GET "https://api.binance.com/api/v3/exchangeInfo?permissions=%5B%22MARGIN%22%2C%22LEVERAGED%22%5D"
     /// or
     /// curl -g -X GET 'https://api.binance.com/api/v3/exchangeInfo?permissions=["MARGIN","LEVERAGED"]'
-    /// TODO: Check serialization.
     pub permissions: Option<Vec<String>>,
     /// Controls whether the content of the permissionSets field is populated or not. Defaults to true
     pub show_permission_sets: Option<bool>,
@@ -57,6 +68,7 @@ pub struct ExchangeInfo {
     pub timezone: String,
     pub server_time: Timestamp,
     pub rate_limits: Vec<RateLimit>,
+    #[serde(default)]
     pub exchange_filters: Vec<ExchangeFilter>,
     pub symbols: Vec<SymbolInfo>,
     /// Optional field. Present only when SOR is available.
@@ -64,6 +76,13 @@ pub struct ExchangeInfo {
     pub sors: Option<Vec<SOR>>,
 }
 
+impl ExchangeInfo {
+    /// Trading rules of the symbol.
+    pub fn symbol(&self, symbol: &str) -> Option<&SymbolInfo> {
+        self.symbols.iter().find(|info| info.symbol == symbol)
+    }
+}
+
 #[derive(Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct RateLimit {
@@ -102,10 +121,83 @@ pub struct SymbolInfo {
     pub allowed_self_trade_prevention_modes: Vec<STPMode>,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct Filter {
-    // TODO:
+/// Symbol filter, defines trading rules of a symbol.
+/// LINK: https://developers.binance.com/docs/binance-spot-api-docs/filters
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(
+    tag = "filterType",
+    rename_all = "SCREAMING_SNAKE_CASE",
+    rename_all_fields = "camelCase"
+)]
+pub enum Filter {
+    /// Price range and tick size. A zero `min_price`, `max_price` or `tick_size` disables that rule.
+    PriceFilter {
+        min_price: Decimal,
+        max_price: Decimal,
+        tick_size: Decimal,
+    },
+    /// Price range relative to the average price of the last `avg_price_mins` minutes.
+    PercentPrice {
+        multiplier_up: Decimal,
+        multiplier_down: Decimal,
+        avg_price_mins: u64,
+    },
+    /// Like `PercentPrice`, with separate ranges for BUY and SELL orders.
+    PercentPriceBySide {
+        bid_multiplier_up: Decimal,
+        bid_multiplier_down: Decimal,
+        ask_multiplier_up: Decimal,
+        ask_multiplier_down: Decimal,
+        avg_price_mins: u64,
+    },
+    /// Quantity range and step size.
+    LotSize {
+        min_qty: Decimal,
+        max_qty: Decimal,
+        step_size: Decimal,
+    },
+    /// Minimum notional value (price * quantity).
+    MinNotional {
+        min_notional: Decimal,
+        apply_to_market: bool,
+        avg_price_mins: u64,
+    },
+    /// Notional value (price * quantity) range.
+    Notional {
+        min_notional: Decimal,
+        apply_min_to_market: bool,
+        max_notional: Decimal,
+        apply_max_to_market: bool,
+        avg_price_mins: u64,
+    },
+    /// Maximum parts of an iceberg order.
+    IcebergParts { limit: u64 },
+    /// Quantity range and step size of MARKET orders.
+    MarketLotSize {
+        min_qty: Decimal,
+        max_qty: Decimal,
+        step_size: Decimal,
+    },
+    /// Maximum open orders on the symbol, including order lists.
+    MaxNumOrders { max_num_orders: u64 },
+    /// Maximum open STOP_LOSS, STOP_LOSS_LIMIT, TAKE_PROFIT and TAKE_PROFIT_LIMIT orders.
+    MaxNumAlgoOrders { max_num_algo_orders: u64 },
+    /// Maximum open iceberg orders.
+    MaxNumIcebergOrders { max_num_iceberg_orders: u64 },
+    /// Maximum position in the base asset, including open BUY orders.
+    MaxPosition { max_position: Decimal },
+    /// Trailing delta range in BIPS.
+    TrailingDelta {
+        min_trailing_above_delta: u64,
+        max_trailing_above_delta: u64,
+        min_trailing_below_delta: u64,
+        max_trailing_below_delta: u64,
+    },
+    /// SELL orders are rejected until `end_time`.
+    TPlusSell { end_time: Option<Timestamp> },
+    /// Filter not known to this version of the SDK.
+    #[serde(other)]
+    Unknown,
 }
 
 /// Smart Order Routing (SOR).
@@ -141,7 +233,7 @@ impl OrderLevel {
         self.0
     }
     pub fn qty(&self) -> Decimal {
-        self.0
+        self.1
     }
 }
 
@@ -324,7 +416,6 @@ pub struct SymbolOrSymbols {
     /// If neither parameter is sent, tickers for all symbols will be returned in an array.
     pub symbol: Option<String>,
     /// Examples of accepted format for the symbols parameter: ["BTCUSDT","BNBUSDT"]
-    /// TODO: check serialization
     /// or
     /// %5B%22BTCUSDT%22,%22BNBUSDT%22%5D
     pub symbols: Option<Vec<String>>,
@@ -333,10 +424,11 @@ pub struct SymbolOrSymbols {
 #[derive(Debug, Deserialize, PartialEq)]
 #[serde(untagged)]
 pub enum TickerPriceChangeStatistic {
-    MiniElement(TickerPriceChangeStatisticMini),
-    MiniList(Vec<TickerPriceChangeStatisticMini>),
+    // Full before Mini, a FULL response also has every MINI field.
     FullElement(TickerPriceChangeStatisticFull),
     FullList(Vec<TickerPriceChangeStatisticFull>),
+    MiniElement(TickerPriceChangeStatisticMini),
+    MiniList(Vec<TickerPriceChangeStatisticMini>),
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
@@ -397,14 +489,919 @@ pub struct TickerPriceChangeStatisticMini {
     pub count: u64,
 }
 
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NewOrderParams {
+    pub symbol: String,
+    pub side: OrderSide,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub time_in_force: Option<TimeInForce>,
+    pub quantity: Option<Decimal>,
+    pub quote_order_qty: Option<Decimal>,
+    pub price: Option<Decimal>,
+    /// A unique id among open orders. Automatically generated if not sent.
+    pub new_client_order_id: Option<String>,
+    pub strategy_id: Option<u64>,
+    /// The value cannot be less than 1000000.
+    pub strategy_type: Option<u64>,
+    /// Used with STOP_LOSS, STOP_LOSS_LIMIT, TAKE_PROFIT, and TAKE_PROFIT_LIMIT orders.
+    pub stop_price: Option<Decimal>,
+    /// Used with STOP_LOSS, STOP_LOSS_LIMIT, TAKE_PROFIT, and TAKE_PROFIT_LIMIT orders.
+    pub trailing_delta: Option<u64>,
+    /// Used with LIMIT, STOP_LOSS_LIMIT, and TAKE_PROFIT_LIMIT to create an iceberg order.
+    pub iceberg_qty: Option<Decimal>,
+    /// Set the response JSON. MARKET and LIMIT order types default to FULL, all other orders default to ACK.
+    pub new_order_resp_type: Option<OrderResponseType>,
+    /// The allowed enums is dependent on what is configured on the symbol.
+    pub self_trade_prevention_mode: Option<STPMode>,
+}
+
+/// Response type depends on `newOrderRespType`.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum OrderResponse {
+    Full(OrderResponseFull),
+    Result(OrderResponseResult),
+    Ack(OrderResponseAck),
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderResponseAck {
+    pub symbol: String,
+    pub order_id: i64,
+    /// Unless it's part of an order list, value will be -1
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub transact_time: Timestamp,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderResponseResult {
+    pub symbol: String,
+    pub order_id: i64,
+    /// Unless it's part of an order list, value will be -1
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub transact_time: Timestamp,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub orig_quote_order_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub side: OrderSide,
+    pub stop_price: Option<Decimal>,
+    pub iceberg_qty: Option<Decimal>,
+    pub trailing_delta: Option<u64>,
+    pub strategy_id: Option<u64>,
+    pub strategy_type: Option<u64>,
+    /// Value is -1 while the order is pending in an order list.
+    pub working_time: Option<i64>,
+    pub self_trade_prevention_mode: STPMode,
+    /// Present only for SOR orders.
+    pub working_floor: Option<WorkingFloor>,
+    /// Present only for SOR orders.
+    pub used_sor: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderResponseFull {
+    #[serde(flatten)]
+    pub result: OrderResponseResult,
+    pub fills: Vec<Fill>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    /// Present only for SOR orders.
+    pub match_type: Option<MatchType>,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub commission: Decimal,
+    pub commission_asset: String,
+    /// Value is -1 for fills allocated by SOR.
+    pub trade_id: i64,
+    /// Present only for SOR orders.
+    pub alloc_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TestNewOrderParams {
+    #[serde(flatten)]
+    pub order: NewOrderParams,
+    /// Default: false
+    pub compute_commission_rates: Option<bool>,
+}
+
+/// Empty unless `computeCommissionRates` is true.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TestNewOrder {
+    pub standard_commission_for_order: Option<OrderCommission>,
+    pub tax_commission_for_order: Option<OrderCommission>,
+    pub discount: Option<CommissionDiscount>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct OrderCommission {
+    pub maker: Decimal,
+    pub taker: Decimal,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionDiscount {
+    pub enabled_for_account: bool,
+    pub enabled_for_symbol: bool,
+    pub discount_asset: String,
+    /// Standard commission is reduced by this rate when paying commission in BNB.
+    pub discount: Decimal,
+}
+
+/// Either orderId or origClientOrderId must be sent.
+/// If both parameters are sent, orderId takes precedence.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryOrderParams {
+    pub symbol: String,
+    pub order_id: Option<i64>,
+    pub orig_client_order_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub symbol: String,
+    pub order_id: i64,
+    /// Unless it's part of an order list, value will be -1
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub side: OrderSide,
+    pub stop_price: Decimal,
+    pub iceberg_qty: Decimal,
+    pub time: Timestamp,
+    pub update_time: Timestamp,
+    pub is_working: bool,
+    /// Value is -1 while the order is pending in an order list.
+    pub working_time: i64,
+    pub orig_quote_order_qty: Decimal,
+    pub self_trade_prevention_mode: STPMode,
+}
+
+/// Either orderId or origClientOrderId must be sent.
+/// If both parameters are sent, orderId takes precedence.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderParams {
+    pub symbol: String,
+    pub order_id: Option<i64>,
+    pub orig_client_order_id: Option<String>,
+    /// Used to uniquely identify this cancel. Automatically generated by default.
+    pub new_client_order_id: Option<String>,
+    pub cancel_restrictions: Option<CancelRestrictions>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CanceledOrder {
+    pub symbol: String,
+    pub orig_client_order_id: String,
+    pub order_id: i64,
+    /// Unless it's part of an order list, value will be -1
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub transact_time: Timestamp,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub orig_quote_order_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub side: OrderSide,
+    pub stop_price: Option<Decimal>,
+    pub iceberg_qty: Option<Decimal>,
+    pub self_trade_prevention_mode: STPMode,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelAllOpenOrdersParams {
+    pub symbol: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetOpenOrdersParams {
+    /// Careful when accessing this with no symbol.
+    pub symbol: Option<String>,
+}
+
+/// Cancels an existing order and places a new order on the same symbol.
+///
+/// Either cancelOrderId or cancelOrigClientOrderId must be sent.
+/// If both parameters are sent, cancelOrderId takes precedence.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelReplaceOrderParams {
+    pub symbol: String,
+    pub side: OrderSide,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub cancel_replace_mode: CancelReplaceMode,
+    pub time_in_force: Option<TimeInForce>,
+    pub quantity: Option<Decimal>,
+    pub quote_order_qty: Option<Decimal>,
+    pub price: Option<Decimal>,
+    /// Used to uniquely identify this cancel. Automatically generated by default.
+    pub cancel_new_client_order_id: Option<String>,
+    pub cancel_orig_client_order_id: Option<String>,
+    pub cancel_order_id: Option<i64>,
+    /// Used to identify the new order.
+    pub new_client_order_id: Option<String>,
+    pub strategy_id: Option<u64>,
+    /// The value cannot be less than 1000000.
+    pub strategy_type: Option<u64>,
+    pub stop_price: Option<Decimal>,
+    pub trailing_delta: Option<u64>,
+    pub iceberg_qty: Option<Decimal>,
+    pub new_order_resp_type: Option<OrderResponseType>,
+    /// The allowed enums is dependent on what is configured on the symbol.
+    pub self_trade_prevention_mode: Option<STPMode>,
+    pub cancel_restrictions: Option<CancelRestrictions>,
+    /// Default: DO_NOTHING
+    pub order_rate_limit_exceeded_mode: Option<OrderRateLimitExceededMode>,
+}
+
+/// Outcome of a cancel-replace operation.
+///
+/// Binance reports a half-done operation as an error (-2021) with both results attached,
+/// here every combination is a separate variant.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(try_from = "CancelReplaceBody")]
+pub enum CancelReplaceOrder {
+    /// The order was canceled and the new order was placed.
+    Success {
+        cancel_response: CanceledOrder,
+        new_order_response: OrderResponse,
+    },
+    /// The order was canceled but the new order was rejected.
+    NewOrderFailed {
+        cancel_response: CanceledOrder,
+        new_order_error: ApiError,
+    },
+    /// The cancel was rejected but the new order was placed (cancelReplaceMode=ALLOW_FAILURE).
+    CancelFailed {
+        cancel_error: ApiError,
+        new_order_response: OrderResponse,
+    },
+    /// The cancel was rejected and the new order was rejected or not attempted (cancelReplaceMode=STOP_ON_FAILURE).
+    Failed {
+        cancel_error: ApiError,
+        new_order_error: Option<ApiError>,
+    },
+}
+
+/// Cancel-replace body is either the data itself or an error that carries the data.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CancelReplaceBody {
+    Error { data: CancelReplaceData },
+    Data(CancelReplaceData),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CancelReplaceData {
+    cancel_result: CancelReplaceStatus,
+    new_order_result: CancelReplaceStatus,
+    cancel_response: Option<ResultOrError<CanceledOrder>>,
+    new_order_response: Option<ResultOrError<OrderResponse>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ResultOrError<T> {
+    Rejected(ApiError),
+    Accepted(T),
+}
+
+impl TryFrom<CancelReplaceBody> for CancelReplaceOrder {
+    type Error = String;
+
+    fn try_from(body: CancelReplaceBody) -> Result<Self, Self::Error> {
+        let data = match body {
+            CancelReplaceBody::Error { data } | CancelReplaceBody::Data(data) => data,
+        };
+
+        use CancelReplaceStatus::{Failure, NotAttempted, Success};
+        use ResultOrError::{Accepted, Rejected};
+        let outcome = match (
+            data.cancel_result,
+            data.cancel_response,
+            data.new_order_result,
+            data.new_order_response,
+        ) {
+            (Success, Some(Accepted(cancel)), Success, Some(Accepted(new_order))) => {
+                Self::Success {
+                    cancel_response: cancel,
+                    new_order_response: new_order,
+                }
+            }
+            (Success, Some(Accepted(cancel)), Failure, Some(Rejected(new_order))) => {
+                Self::NewOrderFailed {
+                    cancel_response: cancel,
+                    new_order_error: new_order,
+                }
+            }
+            (Failure, Some(Rejected(cancel)), Success, Some(Accepted(new_order))) => {
+                Self::CancelFailed {
+                    cancel_error: cancel,
+                    new_order_response: new_order,
+                }
+            }
+            (Failure, Some(Rejected(cancel)), Failure, Some(Rejected(new_order))) => Self::Failed {
+                cancel_error: cancel,
+                new_order_error: Some(new_order),
+            },
+            (Failure, Some(Rejected(cancel)), NotAttempted, None) => Self::Failed {
+                cancel_error: cancel,
+                new_order_error: None,
+            },
+            (cancel, _, new_order, _) => {
+                return Err(format!(
+                    "inconsistent cancel-replace result: cancelResult: {cancel:?}, newOrderResult: {new_order:?}"
+                ));
+            }
+        };
+        Ok(outcome)
+    }
+}
+
+/// Reduce the quantity of an existing open order.
+/// The order keeps its priority in the order book.
+///
+/// Either orderId or origClientOrderId must be sent.
+/// If both parameters are sent, orderId takes precedence.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendOrderKeepPriorityParams {
+    pub symbol: String,
+    pub order_id: Option<i64>,
+    pub orig_client_order_id: Option<String>,
+    /// The new client order ID for the order after being amended.
+    /// If not sent, one will be randomly generated.
+    pub new_client_order_id: Option<String>,
+    /// newQty must be greater than 0 and less than the order's quantity.
+    pub new_qty: Decimal,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendOrderKeepPriority {
+    pub transact_time: Timestamp,
+    pub execution_id: i64,
+    pub amended_order: AmendedOrder,
+    /// Present only when the amended order is part of an order list.
+    pub list_status: Option<AmendedOrderListStatus>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendedOrder {
+    pub symbol: String,
+    pub order_id: i64,
+    /// Unless it's part of an order list, value will be -1
+    pub order_list_id: i64,
+    pub orig_client_order_id: String,
+    pub client_order_id: String,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub executed_qty: Decimal,
+    pub prevented_qty: Decimal,
+    pub quote_order_qty: Decimal,
+    pub cumulative_quote_qty: Decimal,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub side: OrderSide,
+    /// Value is -1 while the order is pending in an order list.
+    pub working_time: i64,
+    pub self_trade_prevention_mode: STPMode,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendedOrderListStatus {
+    pub order_list_id: i64,
+    pub contingency_type: ContingencyType,
+    pub list_order_status: OrderListOrderStatus,
+    pub list_client_order_id: String,
+    pub symbol: String,
+    pub orders: Vec<OrderListOrder>,
+}
+
+/// Places an order using smart order routing (SOR).
+///
+/// SOR only supports LIMIT and MARKET orders. quoteOrderQty is not supported.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NewSOROrderParams {
+    pub symbol: String,
+    pub side: OrderSide,
+    /// Supported values: LIMIT, MARKET
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub time_in_force: Option<TimeInForce>,
+    pub quantity: Decimal,
+    pub price: Option<Decimal>,
+    /// A unique id among open orders. Automatically generated if not sent.
+    pub new_client_order_id: Option<String>,
+    pub strategy_id: Option<u64>,
+    /// The value cannot be less than 1000000.
+    pub strategy_type: Option<u64>,
+    /// Used with LIMIT to create an iceberg order.
+    pub iceberg_qty: Option<Decimal>,
+    /// Set the response JSON. Default: FULL
+    pub new_order_resp_type: Option<OrderResponseType>,
+    /// The allowed enums is dependent on what is configured on the symbol.
+    pub self_trade_prevention_mode: Option<STPMode>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TestSOROrderParams {
+    #[serde(flatten)]
+    pub order: NewSOROrderParams,
+    /// Default: false
+    pub compute_commission_rates: Option<bool>,
+}
+
+/// Retrieves allocations resulting from SOR order placement.
+///
+/// Supported parameter combinations:
+/// symbol - allocations from oldest to newest;
+/// symbol + startTime - oldest allocations since startTime;
+/// symbol + endTime - newest allocations until endTime;
+/// symbol + startTime + endTime - allocations within the time range;
+/// symbol + fromAllocationId - allocations by allocation ID;
+/// symbol + orderId - allocations related to an order starting with oldest;
+/// symbol + orderId + fromAllocationId - allocations related to an order by allocation ID.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAllocationsParams {
+    pub symbol: String,
+    pub start_time: Option<Timestamp>,
+    pub end_time: Option<Timestamp>,
+    pub from_allocation_id: Option<i64>,
+    /// Default: 500; Maximum: 1000.
+    pub limit: Option<u64>,
+    pub order_id: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Allocation {
+    pub symbol: String,
+    pub allocation_id: i64,
+    pub allocation_type: AllocationType,
+    pub order_id: i64,
+    pub order_list_id: i64,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub quote_qty: Decimal,
+    pub commission: Decimal,
+    pub commission_asset: String,
+    pub time: Timestamp,
+    pub is_buyer: bool,
+    pub is_maker: bool,
+    pub is_allocator: bool,
+}
+
+/// One-Cancels-the-Other (OCO) order list.
+///
+/// An OCO has 2 orders called the above order and below order.
+/// One of the orders must be a LIMIT_MAKER/TAKE_PROFIT/TAKE_PROFIT_LIMIT order and the other must be STOP_LOSS or STOP_LOSS_LIMIT order.
+/// Price restrictions:
+/// If the OCO is on the SELL side: LIMIT_MAKER/TAKE_PROFIT_LIMIT price > Last Traded Price > STOP_LOSS/STOP_LOSS_LIMIT stopPrice
+/// If the OCO is on the BUY side: LIMIT_MAKER/TAKE_PROFIT_LIMIT price < Last Traded Price < stopPrice
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NewOrderListOCOParams {
+    pub symbol: String,
+    /// Arbitrary unique ID among open order lists. Automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub side: OrderSide,
+    /// Quantity for both legs of the order list.
+    pub quantity: Decimal,
+    /// Supported values: STOP_LOSS_LIMIT, STOP_LOSS, LIMIT_MAKER, TAKE_PROFIT, TAKE_PROFIT_LIMIT
+    pub above_type: OrderType,
+    pub above_client_order_id: Option<String>,
+    /// Note that this can only be used if aboveTimeInForce is GTC.
+    pub above_iceberg_qty: Option<Decimal>,
+    /// Can be used if aboveType is STOP_LOSS_LIMIT, LIMIT_MAKER, or TAKE_PROFIT_LIMIT to specify the limit price.
+    pub above_price: Option<Decimal>,
+    /// Can be used if aboveType is STOP_LOSS, STOP_LOSS_LIMIT, TAKE_PROFIT, TAKE_PROFIT_LIMIT.
+    /// Either aboveStopPrice or aboveTrailingDelta or both, must be specified.
+    pub above_stop_price: Option<Decimal>,
+    pub above_trailing_delta: Option<u64>,
+    /// Required if aboveType is STOP_LOSS_LIMIT or TAKE_PROFIT_LIMIT
+    pub above_time_in_force: Option<TimeInForce>,
+    pub above_strategy_id: Option<u64>,
+    /// The value cannot be less than 1000000.
+    pub above_strategy_type: Option<u64>,
+    /// Supported values: STOP_LOSS, STOP_LOSS_LIMIT, TAKE_PROFIT, TAKE_PROFIT_LIMIT
+    pub below_type: OrderType,
+    pub below_client_order_id: Option<String>,
+    /// Note that this can only be used if belowTimeInForce is GTC.
+    pub below_iceberg_qty: Option<Decimal>,
+    /// Can be used if belowType is STOP_LOSS_LIMIT, LIMIT_MAKER, or TAKE_PROFIT_LIMIT to specify the limit price.
+    pub below_price: Option<Decimal>,
+    /// Can be used if belowType is STOP_LOSS, STOP_LOSS_LIMIT, TAKE_PROFIT or TAKE_PROFIT_LIMIT.
+    /// Either belowStopPrice or belowTrailingDelta or both, must be specified.
+    pub below_stop_price: Option<Decimal>,
+    pub below_trailing_delta: Option<u64>,
+    /// Required if belowType is STOP_LOSS_LIMIT or TAKE_PROFIT_LIMIT
+    pub below_time_in_force: Option<TimeInForce>,
+    pub below_strategy_id: Option<u64>,
+    /// The value cannot be less than 1000000.
+    pub below_strategy_type: Option<u64>,
+    pub new_order_resp_type: Option<OrderResponseType>,
+    pub self_trade_prevention_mode: Option<STPMode>,
+}
+
+/// One-Triggers-the-Other (OTO) order list.
+///
+/// An OTO has a working order and a pending order.
+/// The pending order is placed only when the working order is fully filled.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NewOrderListOTOParams {
+    pub symbol: String,
+    /// Arbitrary unique ID among open order lists. Automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub new_order_resp_type: Option<OrderResponseType>,
+    pub self_trade_prevention_mode: Option<STPMode>,
+    /// Supported values: LIMIT, LIMIT_MAKER
+    pub working_type: OrderType,
+    pub working_side: OrderSide,
+    pub working_client_order_id: Option<String>,
+    pub working_price: Decimal,
+    pub working_quantity: Decimal,
+    /// This can only be used if workingTimeInForce is GTC, or if workingType is LIMIT_MAKER.
+    pub working_iceberg_qty: Option<Decimal>,
+    pub working_time_in_force: Option<TimeInForce>,
+    pub working_strategy_id: Option<u64>,
+    /// The value cannot be less than 1000000.
+    pub working_strategy_type: Option<u64>,
+    pub pending_type: OrderType,
+    pub pending_side: OrderSide,
+    pub pending_client_order_id: Option<String>,
+    pub pending_price: Option<Decimal>,
+    pub pending_stop_price: Option<Decimal>,
+    pub pending_trailing_delta: Option<u64>,
+    pub pending_quantity: Decimal,
+    /// This can only be used if pendingTimeInForce is GTC, or if pendingType is LIMIT_MAKER.
+    pub pending_iceberg_qty: Option<Decimal>,
+    pub pending_time_in_force: Option<TimeInForce>,
+    pub pending_strategy_id: Option<u64>,
+    /// The value cannot be less than 1000000.
+    pub pending_strategy_type: Option<u64>,
+}
+
+/// One-Triggers-a-One-Cancels-the-Other (OTOCO) order list.
+///
+/// An OTOCO has a working order and an OCO pair of pending orders.
+/// The pending orders are placed only when the working order is fully filled.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NewOrderListOTOCOParams {
+    pub symbol: String,
+    /// Arbitrary unique ID among open order lists. Automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub new_order_resp_type: Option<OrderResponseType>,
+    pub self_trade_prevention_mode: Option<STPMode>,
+    /// Supported values: LIMIT, LIMIT_MAKER
+    pub working_type: OrderType,
+    pub working_side: OrderSide,
+    pub working_client_order_id: Option<String>,
+    pub working_price: Decimal,
+    pub working_quantity: Decimal,
+    /// This can only be used if workingTimeInForce is GTC, or if workingType is LIMIT_MAKER.
+    pub working_iceberg_qty: Option<Decimal>,
+    pub working_time_in_force: Option<TimeInForce>,
+    pub working_strategy_id: Option<u64>,
+    /// The value cannot be less than 1000000.
+    pub working_strategy_type: Option<u64>,
+    pub pending_side: OrderSide,
+    pub pending_quantity: Decimal,
+    /// Supported values: STOP_LOSS_LIMIT, STOP_LOSS, LIMIT_MAKER, TAKE_PROFIT, TAKE_PROFIT_LIMIT
+    pub pending_above_type: OrderType,
+    pub pending_above_client_order_id: Option<String>,
+    pub pending_above_price: Option<Decimal>,
+    pub pending_above_stop_price: Option<Decimal>,
+    pub pending_above_trailing_delta: Option<u64>,
+    /// This can only be used if pendingAboveTimeInForce is GTC, or if pendingAboveType is LIMIT_MAKER.
+    pub pending_above_iceberg_qty: Option<Decimal>,
+    pub pending_above_time_in_force: Option<TimeInForce>,
+    pub pending_above_strategy_id: Option<u64>,
+    /// The value cannot be less than 1000000.
+    pub pending_above_strategy_type: Option<u64>,
+    /// Supported values: STOP_LOSS, STOP_LOSS_LIMIT, TAKE_PROFIT, TAKE_PROFIT_LIMIT
+    pub pending_below_type: Option<OrderType>,
+    pub pending_below_client_order_id: Option<String>,
+    pub pending_below_price: Option<Decimal>,
+    pub pending_below_stop_price: Option<Decimal>,
+    pub pending_below_trailing_delta: Option<u64>,
+    /// This can only be used if pendingBelowTimeInForce is GTC, or if pendingBelowType is LIMIT_MAKER.
+    pub pending_below_iceberg_qty: Option<Decimal>,
+    pub pending_below_time_in_force: Option<TimeInForce>,
+    pub pending_below_strategy_id: Option<u64>,
+    /// The value cannot be less than 1000000.
+    pub pending_below_strategy_type: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderList {
+    pub order_list_id: i64,
+    pub contingency_type: ContingencyType,
+    pub list_status_type: OrderListStatus,
+    pub list_order_status: OrderListOrderStatus,
+    pub list_client_order_id: String,
+    pub transaction_time: Timestamp,
+    pub symbol: String,
+    pub orders: Vec<OrderListOrder>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListOrder {
+    pub symbol: String,
+    pub order_id: i64,
+    pub client_order_id: String,
+}
+
+/// Placed order list with reports of every order, format depends on `newOrderRespType`.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListResponse {
+    #[serde(flatten)]
+    pub list: OrderList,
+    #[serde(default)]
+    pub order_reports: Vec<OrderResponse>,
+}
+
+/// Either orderListId or origClientOrderId must be provided.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryOrderListParams {
+    pub order_list_id: Option<i64>,
+    pub orig_client_order_id: Option<String>,
+}
+
+/// Either orderListId or listClientOrderId must be provided.
+/// Canceling an individual order from an order list will cancel the entire order list.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderListParams {
+    pub symbol: String,
+    pub order_list_id: Option<i64>,
+    pub list_client_order_id: Option<String>,
+    /// Used to uniquely identify this cancel. Automatically generated by default.
+    pub new_client_order_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CanceledOrderList {
+    #[serde(flatten)]
+    pub list: OrderList,
+    pub order_reports: Vec<CanceledOrder>,
+}
+
+/// Cancel all open orders returns canceled orders and canceled order lists in one array.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum CanceledOpenOrder {
+    OrderList(CanceledOrderList),
+    Order(CanceledOrder),
+}
+
+/// Current unfilled order count for one ORDERS rate limit.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnfilledOrderCount {
+    pub rate_limit_type: RateLimiter,
+    pub interval: RateLimitInterval,
+    pub interval_num: u64,
+    pub limit: u64,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAccountInformationParams {
+    /// When set to true, emits only the non-zero balances of an account.
+    /// Default value: false
+    pub omit_zero_balances: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInformation {
+    pub maker_commission: u64,
+    pub taker_commission: u64,
+    pub buyer_commission: u64,
+    pub seller_commission: u64,
+    pub commission_rates: CommissionRates,
+    pub can_trade: bool,
+    pub can_withdraw: bool,
+    pub can_deposit: bool,
+    pub brokered: bool,
+    pub require_self_trade_prevention: bool,
+    pub prevent_sor: bool,
+    pub update_time: Timestamp,
+    pub account_type: String,
+    pub balances: Vec<Balance>,
+    pub permissions: Vec<String>,
+    pub uid: u64,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct CommissionRates {
+    pub maker: Decimal,
+    pub taker: Decimal,
+    pub buyer: Decimal,
+    pub seller: Decimal,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Balance {
+    pub asset: String,
+    pub free: Decimal,
+    pub locked: Decimal,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKey {
+    pub listen_key: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKeyParams {
+    pub listen_key: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct KeepaliveUserDataStream {}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct CloseUserDataStream {}
+
 #[cfg(test)]
 mod tests {
     use rust_decimal::dec;
 
-    use crate::spot::serde::deserialize_str;
+    use crate::spot::{ErrorCode, serde::deserialize_str};
 
     use super::*;
 
+    #[test]
+    fn deserialize_exchange_filters() {
+        let json = r#"[
+            {"filterType": "EXCHANGE_MAX_NUM_ORDERS", "maxNumOrders": 1000},
+            {"filterType": "EXCHANGE_MAX_NUM_ALGO_ORDERS", "maxNumAlgoOrders": 200},
+            {"filterType": "EXCHANGE_MAX_NUM_ICEBERG_ORDERS", "maxNumIcebergOrders": 10000},
+            {"filterType": "EXCHANGE_NEW_FILTER", "limit": 1}
+        ]"#;
+        let expected = vec![
+            ExchangeFilter::ExchangeMaxNumOrders {
+                max_num_orders: 1000,
+            },
+            ExchangeFilter::ExchangeMaxNumAlgoOrders {
+                max_num_algo_orders: 200,
+            },
+            ExchangeFilter::ExchangeMaxNumIcebergOrders {
+                max_num_iceberg_orders: 10000,
+            },
+            ExchangeFilter::Unknown,
+        ];
+
+        let current: Vec<ExchangeFilter> = deserialize_str(json).unwrap();
+        assert_eq!(current, expected);
+    }
+
+    #[test]
+    fn deserialize_symbol_filters() {
+        let json = r#"[
+            {"filterType": "PRICE_FILTER", "minPrice": "0.00000100", "maxPrice": "100000.00000000", "tickSize": "0.00000100"},
+            {"filterType": "PERCENT_PRICE", "multiplierUp": "1.3000", "multiplierDown": "0.7000", "avgPriceMins": 5},
+            {"filterType": "PERCENT_PRICE_BY_SIDE", "bidMultiplierUp": "1.2", "bidMultiplierDown": "0.2", "askMultiplierUp": "5", "askMultiplierDown": "0.8", "avgPriceMins": 1},
+            {"filterType": "LOT_SIZE", "minQty": "0.00100000", "maxQty": "100000.00000000", "stepSize": "0.00100000"},
+            {"filterType": "MIN_NOTIONAL", "minNotional": "0.00100000", "applyToMarket": true, "avgPriceMins": 5},
+            {"filterType": "NOTIONAL", "minNotional": "10.00000000", "applyMinToMarket": false, "maxNotional": "10000.00000000", "applyMaxToMarket": false, "avgPriceMins": 5},
+            {"filterType": "ICEBERG_PARTS", "limit": 10},
+            {"filterType": "MARKET_LOT_SIZE", "minQty": "0.00000000", "maxQty": "76.00000000", "stepSize": "0.00000000"},
+            {"filterType": "MAX_NUM_ORDERS", "maxNumOrders": 200},
+            {"filterType": "MAX_NUM_ALGO_ORDERS", "maxNumAlgoOrders": 5},
+            {"filterType": "MAX_NUM_ICEBERG_ORDERS", "maxNumIcebergOrders": 5},
+            {"filterType": "MAX_POSITION", "maxPosition": "10.00000000"},
+            {"filterType": "TRAILING_DELTA", "minTrailingAboveDelta": 10, "maxTrailingAboveDelta": 2000, "minTrailingBelowDelta": 10, "maxTrailingBelowDelta": 2000},
+            {"filterType": "T_PLUS_SELL", "endTime": 1760054400000},
+            {"filterType": "NEW_FILTER", "limit": 1}
+        ]"#;
+        let expected = vec![
+            Filter::PriceFilter {
+                min_price: dec!(0.000001),
+                max_price: dec!(100000),
+                tick_size: dec!(0.000001),
+            },
+            Filter::PercentPrice {
+                multiplier_up: dec!(1.3),
+                multiplier_down: dec!(0.7),
+                avg_price_mins: 5,
+            },
+            Filter::PercentPriceBySide {
+                bid_multiplier_up: dec!(1.2),
+                bid_multiplier_down: dec!(0.2),
+                ask_multiplier_up: dec!(5),
+                ask_multiplier_down: dec!(0.8),
+                avg_price_mins: 1,
+            },
+            Filter::LotSize {
+                min_qty: dec!(0.001),
+                max_qty: dec!(100000),
+                step_size: dec!(0.001),
+            },
+            Filter::MinNotional {
+                min_notional: dec!(0.001),
+                apply_to_market: true,
+                avg_price_mins: 5,
+            },
+            Filter::Notional {
+                min_notional: dec!(10),
+                apply_min_to_market: false,
+                max_notional: dec!(10000),
+                apply_max_to_market: false,
+                avg_price_mins: 5,
+            },
+            Filter::IcebergParts { limit: 10 },
+            Filter::MarketLotSize {
+                min_qty: dec!(0),
+                max_qty: dec!(76),
+                step_size: dec!(0),
+            },
+            Filter::MaxNumOrders {
+                max_num_orders: 200,
+            },
+            Filter::MaxNumAlgoOrders {
+                max_num_algo_orders: 5,
+            },
+            Filter::MaxNumIcebergOrders {
+                max_num_iceberg_orders: 5,
+            },
+            Filter::MaxPosition {
+                max_position: dec!(10),
+            },
+            Filter::TrailingDelta {
+                min_trailing_above_delta: 10,
+                max_trailing_above_delta: 2000,
+                min_trailing_below_delta: 10,
+                max_trailing_below_delta: 2000,
+            },
+            Filter::TPlusSell {
+                end_time: Some(1760054400000),
+            },
+            Filter::Unknown,
+        ];
+
+        let current: Vec<Filter> = deserialize_str(json).unwrap();
+        assert_eq!(current, expected);
+    }
+
     #[test]
     fn deserialize_response_exchange_info() {
         let json = r#"{
@@ -541,4 +1538,380 @@ mod tests {
 
         assert_eq!(expected, current);
     }
+
+    #[test]
+    fn serialize_params_test_new_order() {
+        let params = TestNewOrderParams {
+            order: NewOrderParams {
+                symbol: String::from("LTCBTC"),
+                side: OrderSide::BUY,
+                order_type: OrderType::Limit,
+                time_in_force: Some(TimeInForce::GTC),
+                quantity: Some(dec!(1)),
+                quote_order_qty: None,
+                price: Some(dec!(0.1)),
+                new_client_order_id: None,
+                strategy_id: None,
+                strategy_type: None,
+                stop_price: None,
+                trailing_delta: None,
+                iceberg_qty: None,
+                new_order_resp_type: Some(OrderResponseType::ACK),
+                self_trade_prevention_mode: None,
+            },
+            compute_commission_rates: Some(true),
+        };
+        let expected = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&newOrderRespType=ACK&computeCommissionRates=true";
+
+        let current = serde_urlencoded::to_string(&params).unwrap();
+
+        assert_eq!(expected, current);
+    }
+
+    #[test]
+    fn deserialize_response_new_order_full() {
+        let json = r#"{
+            "symbol": "BTCUSDT",
+            "orderId": 28,
+            "orderListId": -1,
+            "clientOrderId": "6gCrw2kRUAF9CvJDGP16IP",
+            "transactTime": 1507725176595,
+            "price": "0.00000000",
+            "origQty": "10.00000000",
+            "executedQty": "10.00000000",
+            "origQuoteOrderQty": "0.000000",
+            "cummulativeQuoteQty": "10.00000000",
+            "status": "FILLED",
+            "timeInForce": "GTC",
+            "type": "MARKET",
+            "side": "SELL",
+            "workingTime": 1507725176595,
+            "selfTradePreventionMode": "NONE",
+            "fills": [
+                {
+                    "price": "4000.00000000",
+                    "qty": "1.00000000",
+                    "commission": "4.00000000",
+                    "commissionAsset": "USDT",
+                    "tradeId": 56
+                }
+            ]
+        }"#;
+        let expected = OrderResponse::Full(OrderResponseFull {
+            result: OrderResponseResult {
+                symbol: String::from("BTCUSDT"),
+                order_id: 28,
+                order_list_id: -1,
+                client_order_id: String::from("6gCrw2kRUAF9CvJDGP16IP"),
+                transact_time: 1507725176595,
+                price: dec!(0.00000000),
+                orig_qty: dec!(10.00000000),
+                executed_qty: dec!(10.00000000),
+                orig_quote_order_qty: dec!(0.000000),
+                cummulative_quote_qty: dec!(10.00000000),
+                status: OrderStatus::Filled,
+                time_in_force: TimeInForce::GTC,
+                order_type: OrderType::Market,
+                side: OrderSide::SELL,
+                stop_price: None,
+                iceberg_qty: None,
+                trailing_delta: None,
+                strategy_id: None,
+                strategy_type: None,
+                working_time: Some(1507725176595),
+                self_trade_prevention_mode: STPMode::None,
+                working_floor: None,
+                used_sor: None,
+            },
+            fills: vec![Fill {
+                match_type: None,
+                price: dec!(4000.00000000),
+                qty: dec!(1.00000000),
+                commission: dec!(4.00000000),
+                commission_asset: String::from("USDT"),
+                trade_id: 56,
+                alloc_id: None,
+            }],
+        });
+
+        let current = deserialize_str(json).unwrap();
+
+        assert_eq!(expected, current);
+    }
+
+    #[test]
+    fn deserialize_response_new_order_ack() {
+        let json = r#"{
+            "symbol": "BTCUSDT",
+            "orderId": 28,
+            "orderListId": -1,
+            "clientOrderId": "6gCrw2kRUAF9CvJDGP16IP",
+            "transactTime": 1507725176595
+        }"#;
+        let expected = OrderResponse::Ack(OrderResponseAck {
+            symbol: String::from("BTCUSDT"),
+            order_id: 28,
+            order_list_id: -1,
+            client_order_id: String::from("6gCrw2kRUAF9CvJDGP16IP"),
+            transact_time: 1507725176595,
+        });
+
+        let current = deserialize_str(json).unwrap();
+
+        assert_eq!(expected, current);
+    }
+
+    #[test]
+    fn deserialize_response_new_order_list_oco() {
+        let json = r#"{
+            "orderListId": 1,
+            "contingencyType": "OCO",
+            "listStatusType": "EXEC_STARTED",
+            "listOrderStatus": "EXECUTING",
+            "listClientOrderId": "lH1YDkuQKWiXVXHPSKYEIp",
+            "transactionTime": 1710485608839,
+            "symbol": "LTCBTC",
+            "orders": [
+                {
+                    "symbol": "LTCBTC",
+                    "orderId": 10,
+                    "clientOrderId": "44nZvqpemY7sVYgPYbvPih"
+                }
+            ],
+            "orderReports": [
+                {
+                    "symbol": "LTCBTC",
+                    "orderId": 10,
+                    "orderListId": 1,
+                    "clientOrderId": "44nZvqpemY7sVYgPYbvPih",
+                    "transactTime": 1710485608839,
+                    "price": "1.00000000",
+                    "origQty": "5.00000000",
+                    "executedQty": "0.00000000",
+                    "origQuoteOrderQty": "0.000000",
+                    "cummulativeQuoteQty": "0.00000000",
+                    "status": "NEW",
+                    "timeInForce": "GTC",
+                    "type": "STOP_LOSS_LIMIT",
+                    "side": "SELL",
+                    "stopPrice": "1.00000000",
+                    "workingTime": -1,
+                    "icebergQty": "1.00000000",
+                    "selfTradePreventionMode": "NONE"
+                }
+            ]
+        }"#;
+        let expected = OrderListResponse {
+            list: OrderList {
+                order_list_id: 1,
+                contingency_type: ContingencyType::OCO,
+                list_status_type: OrderListStatus::ExecStarted,
+                list_order_status: OrderListOrderStatus::Executing,
+                list_client_order_id: String::from("lH1YDkuQKWiXVXHPSKYEIp"),
+                transaction_time: 1710485608839,
+                symbol: String::from("LTCBTC"),
+                orders: vec![OrderListOrder {
+                    symbol: String::from("LTCBTC"),
+                    order_id: 10,
+                    client_order_id: String::from("44nZvqpemY7sVYgPYbvPih"),
+                }],
+            },
+            order_reports: vec![OrderResponse::Result(OrderResponseResult {
+                symbol: String::from("LTCBTC"),
+                order_id: 10,
+                order_list_id: 1,
+                client_order_id: String::from("44nZvqpemY7sVYgPYbvPih"),
+                transact_time: 1710485608839,
+                price: dec!(1.00000000),
+                orig_qty: dec!(5.00000000),
+                executed_qty: dec!(0.00000000),
+                orig_quote_order_qty: dec!(0.000000),
+                cummulative_quote_qty: dec!(0.00000000),
+                status: OrderStatus::New,
+                time_in_force: TimeInForce::GTC,
+                order_type: OrderType::StopLossLimit,
+                side: OrderSide::SELL,
+                stop_price: Some(dec!(1.00000000)),
+                iceberg_qty: Some(dec!(1.00000000)),
+                trailing_delta: None,
+                strategy_id: None,
+                strategy_type: None,
+                working_time: Some(-1),
+                self_trade_prevention_mode: STPMode::None,
+                working_floor: None,
+                used_sor: None,
+            })],
+        };
+
+        let current = deserialize_str(json).unwrap();
+
+        assert_eq!(expected, current);
+    }
+
+    #[test]
+    fn deserialize_response_cancel_replace_partially_failed() {
+        let json = r#"{
+            "code": -2021,
+            "msg": "Order cancel-replace partially failed.",
+            "data": {
+                "cancelResult": "SUCCESS",
+                "newOrderResult": "FAILURE",
+                "cancelResponse": {
+                    "symbol": "BTCUSDT",
+                    "origClientOrderId": "86M8erehfExV8z2RC8Zo8k",
+                    "orderId": 3,
+                    "orderListId": -1,
+                    "clientOrderId": "G1kLo6aDv2KGNTFcjfTSFq",
+                    "transactTime": 1684804350068,
+                    "price": "0.01000000",
+                    "origQty": "0.000100",
+                    "executedQty": "0.00000000",
+                    "origQuoteOrderQty": "0.000000",
+                    "cummulativeQuoteQty": "0.00000000",
+                    "status": "CANCELED",
+                    "timeInForce": "GTC",
+                    "type": "LIMIT_MAKER",
+                    "side": "SELL",
+                    "selfTradePreventionMode": "NONE"
+                },
+                "newOrderResponse": {
+                    "code": -2010,
+                    "msg": "Order would immediately match and take."
+                }
+            }
+        }"#;
+        let expected = CancelReplaceOrder::NewOrderFailed {
+            cancel_response: CanceledOrder {
+                symbol: String::from("BTCUSDT"),
+                orig_client_order_id: String::from("86M8erehfExV8z2RC8Zo8k"),
+                order_id: 3,
+                order_list_id: -1,
+                client_order_id: String::from("G1kLo6aDv2KGNTFcjfTSFq"),
+                transact_time: 1684804350068,
+                price: dec!(0.01000000),
+                orig_qty: dec!(0.000100),
+                executed_qty: dec!(0.00000000),
+                orig_quote_order_qty: dec!(0.000000),
+                cummulative_quote_qty: dec!(0.00000000),
+                status: OrderStatus::Canceled,
+                time_in_force: TimeInForce::GTC,
+                order_type: OrderType::LimitMaker,
+                side: OrderSide::SELL,
+                stop_price: None,
+                iceberg_qty: None,
+                self_trade_prevention_mode: STPMode::None,
+            },
+            new_order_error: ApiError {
+                code: ErrorCode::NewOrderRejected,
+                msg: String::from("Order would immediately match and take."),
+            },
+        };
+
+        let current = deserialize_str(json).unwrap();
+
+        assert_eq!(expected, current);
+    }
+
+    #[test]
+    fn deserialize_response_cancel_replace_failed() {
+        let json = r#"{
+            "code": -2022,
+            "msg": "Order cancel-replace failed.",
+            "data": {
+                "cancelResult": "FAILURE",
+                "newOrderResult": "NOT_ATTEMPTED",
+                "cancelResponse": {
+                    "code": -2011,
+                    "msg": "Unknown order sent."
+                },
+                "newOrderResponse": null
+            }
+        }"#;
+        let expected = CancelReplaceOrder::Failed {
+            cancel_error: ApiError {
+                code: ErrorCode::CancelRejected,
+                msg: String::from("Unknown order sent."),
+            },
+            new_order_error: None,
+        };
+
+        let current = deserialize_str(json).unwrap();
+
+        assert_eq!(expected, current);
+    }
+
+    #[test]
+    fn deserialize_response_new_sor_order_full() {
+        let json = r#"{
+            "symbol": "BTCUSDT",
+            "orderId": 2,
+            "orderListId": -1,
+            "clientOrderId": "sBI1KM6nNtOfj5tccZSKly",
+            "transactTime": 1689149087774,
+            "price": "31000.00000000",
+            "origQty": "0.50000000",
+            "executedQty": "0.50000000",
+            "origQuoteOrderQty": "0.000000",
+            "cummulativeQuoteQty": "14000.00000000",
+            "status": "FILLED",
+            "timeInForce": "GTC",
+            "type": "LIMIT",
+            "side": "BUY",
+            "workingTime": 1689149087774,
+            "fills": [
+                {
+                    "matchType": "ONE_PARTY_TRADE_REPORT",
+                    "price": "28000.00000000",
+                    "qty": "0.50000000",
+                    "commission": "0.00000000",
+                    "commissionAsset": "BTC",
+                    "tradeId": -1,
+                    "allocId": 0
+                }
+            ],
+            "workingFloor": "SOR",
+            "selfTradePreventionMode": "NONE",
+            "usedSor": true
+        }"#;
+        let expected = OrderResponse::Full(OrderResponseFull {
+            result: OrderResponseResult {
+                symbol: String::from("BTCUSDT"),
+                order_id: 2,
+                order_list_id: -1,
+                client_order_id: String::from("sBI1KM6nNtOfj5tccZSKly"),
+                transact_time: 1689149087774,
+                price: dec!(31000.00000000),
+                orig_qty: dec!(0.50000000),
+                executed_qty: dec!(0.50000000),
+                orig_quote_order_qty: dec!(0.000000),
+                cummulative_quote_qty: dec!(14000.00000000),
+                status: OrderStatus::Filled,
+                time_in_force: TimeInForce::GTC,
+                order_type: OrderType::Limit,
+                side: OrderSide::BUY,
+                stop_price: None,
+                iceberg_qty: None,
+                trailing_delta: None,
+                strategy_id: None,
+                strategy_type: None,
+                working_time: Some(1689149087774),
+                self_trade_prevention_mode: STPMode::None,
+                working_floor: Some(WorkingFloor::SOR),
+                used_sor: Some(true),
+            },
+            fills: vec![Fill {
+                match_type: Some(MatchType::OnePartyTradeReport),
+                price: dec!(28000.00000000),
+                qty: dec!(0.50000000),
+                commission: dec!(0.00000000),
+                commission_asset: String::from("BTC"),
+                trade_id: -1,
+                alloc_id: Some(0),
+            }],
+        });
+
+        let current = deserialize_str(json).unwrap();
+
+        assert_eq!(expected, current);
+    }
 }
@@ -1,57 +1,409 @@
-use reqwest::{self, Method, RequestBuilder, header::HeaderMap};
+use std::{
+    collections::BTreeMap,
+    net::IpAddr,
+    sync::{Arc, Mutex, PoisonError, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::{
+    self, Method,
+    header::{CONTENT_TYPE, HeaderMap, HeaderValue},
+};
+use serde::Serialize;
 
 use crate::spot::{
-    AggregateTrade, CurrentAveragePrice, GetAggregateTradesParams, GetCurrentAveragePriceParams,
-    GetKlineListParams, GetOlderTradesParams, GetOrderBookParams, GetRecentTradesParams,
-    GetTickerPriceChangeStatisticsParams, Kline, OrderBook, RecentTrade, TestConnectivity,
-    TickerPriceChangeStatistic,
+    AccountInformation, AggregateTrade, Allocation, AmendOrderKeepPriority,
+    AmendOrderKeepPriorityParams, CancelAllOpenOrdersParams, CancelOrderListParams,
+    CancelOrderParams, CancelReplaceOrder, CancelReplaceOrderParams, CanceledOpenOrder,
+    CanceledOrder, CanceledOrderList, CloseUserDataStream, CurrentAveragePrice,
+    GetAccountInformationParams, GetAggregateTradesParams, GetAllocationsParams,
+    GetCurrentAveragePriceParams, GetKlineListParams, GetOlderTradesParams, GetOpenOrdersParams,
+    GetOrderBookParams, GetRecentTradesParams, GetTickerPriceChangeStatisticsParams, HttpRequest,
+    HttpTransport, KeepaliveUserDataStream, Kline, ListenKey, ListenKeyParams,
+    NewOrderListOCOParams, NewOrderListOTOCOParams, NewOrderListOTOParams, NewOrderParams,
+    NewSOROrderParams, Order, OrderBook, OrderList, OrderListResponse, OrderResponse,
+    QueryOrderListParams, QueryOrderParams, RateLimitUsage, RateLimitWindow, RecentTrade,
+    RequestLimiter, ReqwestTransport, RetryPolicy, TestConnectivity, TestNewOrder,
+    TestNewOrderParams, TestSOROrderParams, ThrottleMode, TickerPriceChangeStatistic, Timestamp,
+    UnfilledOrderCount, request_weight,
 };
 
 use super::{
-    Error, ExchangeInfo, GetExchangeInfoParams, Headers, Response, ServerTime,
-    crypto::SensitiveString, serde::deserialize_str, url::*,
+    ApiError, Error, ErrorCode, ExchangeInfo, GetExchangeInfoParams, Headers, Response,
+    SecurityType, ServerTime,
+    crypto::{SensitiveString, Signer},
+    serde::{deserialize_str, to_query},
+    url::*,
 };
 
 pub struct ClientConfig {
     pub base_url: String,
     pub api_key: Option<SensitiveString>,
+    /// HMAC secret key or PKCS#8 PEM encoded RSA/Ed25519 private key.
+    /// Required for TRADE and USER_DATA endpoints.
     pub api_secret: Option<SensitiveString>,
+    /// The number of milliseconds after timestamp the request is valid for.
+    /// Default: 5000; Maximum: 60000.
+    pub recv_window: Option<u64>,
+    /// Retry policy for failed requests, see [`RetryPolicy`].
+    pub retry: RetryPolicy,
+    /// Timeout for establishing a connection.
+    pub connect_timeout: Option<Duration>,
+    /// Timeout for every read from an established connection.
+    pub read_timeout: Option<Duration>,
+    /// Proxy URL for all requests, e.g. `http://127.0.0.1:3128` or `socks5://127.0.0.1:1080`.
+    pub proxy: Option<String>,
+    /// `User-Agent` header value.
+    pub user_agent: Option<String>,
+    /// TCP keepalive interval for pooled connections.
+    pub tcp_keepalive: Option<Duration>,
+    /// Local address to bind outgoing connections to.
+    pub local_address: Option<IpAddr>,
+    /// Pre-built HTTP client to use instead of building one from the options above.
+    pub http_client: Option<reqwest::Client>,
+    /// Transport to send requests with instead of reqwest, e.g. [`CannedTransport`] in tests.
+    /// Connection options above are ignored when set.
+    pub transport: Option<Arc<dyn HttpTransport>>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            base_url: BASE_URL_API.to_string(),
+            api_key: None,
+            api_secret: None,
+            recv_window: None,
+            retry: RetryPolicy::default(),
+            connect_timeout: None,
+            read_timeout: None,
+            proxy: None,
+            user_agent: None,
+            tcp_keepalive: None,
+            local_address: None,
+            http_client: None,
+            transport: None,
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Build HTTP client from the connection options, unless one was provided.
+    fn http_client(&self) -> Result<reqwest::Client, Error> {
+        if let Some(client) = &self.http_client {
+            return Ok(client.clone());
+        }
+
+        let mut builder = reqwest::Client::builder()
+            .tcp_keepalive(self.tcp_keepalive)
+            .local_address(self.local_address);
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+
+        let client = builder.build()?;
+        Ok(client)
+    }
 }
 
 pub struct Client {
     base_url: String,
+    transport: Arc<dyn HttpTransport>,
     cfg: ClientConfig,
+    signer: Option<Signer>,
+    usage: Arc<RwLock<RateLimitUsage>>,
+    limiter: Mutex<Option<RequestLimiter>>,
+}
+
+/// Request parameters with its request weight, built into an HTTP request on every attempt.
+struct PreparedRequest {
+    method: Method,
+    path: Path,
+    security: SecurityType,
+    query: String,
+    weight: u64,
 }
 
 impl Client {
-    pub fn new(cfg: ClientConfig) -> Self {
-        Self {
+    /// Fails when `api_secret` looks like a PEM key but is neither RSA nor Ed25519,
+    /// or when the HTTP client can not be built from the connection options.
+    pub fn new(cfg: ClientConfig) -> Result<Self, Error> {
+        let signer = cfg.api_secret.as_ref().map(Signer::new).transpose()?;
+        let transport = match &cfg.transport {
+            Some(transport) => Arc::clone(transport),
+            None => Arc::new(ReqwestTransport::new(cfg.http_client()?)),
+        };
+
+        Ok(Self {
             base_url: cfg.base_url.clone(),
+            transport,
             cfg,
+            signer,
+            usage: Arc::default(),
+            limiter: Mutex::default(),
+        })
+    }
+
+    /// Enable client-side throttling with REQUEST_WEIGHT and RAW_REQUESTS limits from exchange info.
+    /// Costs one exchange info request (weight 20).
+    pub async fn enable_rate_limiter(&self, mode: ThrottleMode) -> Result<(), Error> {
+        let params = GetExchangeInfoParams {
+            symbol: None,
+            symbols: None,
+            permissions: None,
+            show_permission_sets: Some(false),
+            symbol_status: None,
+        };
+        let response = self.get_exchange_info(params).await?;
+
+        let limiter = RequestLimiter::new(&response.result.rate_limits, mode);
+        self.set_rate_limiter(Some(limiter));
+        Ok(())
+    }
+
+    /// Replace or remove (None) the client-side limiter.
+    pub fn set_rate_limiter(&self, limiter: Option<RequestLimiter>) {
+        let mut current = self.limiter.lock().unwrap_or_else(PoisonError::into_inner);
+        *current = limiter;
+    }
+
+    /// Latest X-MBX-USED-WEIGHT-* and X-MBX-ORDER-COUNT-* values seen by this client.
+    pub fn rate_limit_usage(&self) -> RateLimitUsage {
+        self.usage
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Shared handle to the rate limit usage snapshot, updated after every response.
+    pub fn rate_limit_usage_handle(&self) -> Arc<RwLock<RateLimitUsage>> {
+        Arc::clone(&self.usage)
+    }
+
+    /// Prepare request for endpoint with given security type.
+    fn request<P>(
+        &self,
+        method: Method,
+        path: Path,
+        security: SecurityType,
+        params: &P,
+    ) -> Result<PreparedRequest, Error>
+    where
+        P: Serialize + ?Sized,
+    {
+        let query = to_query(params)?;
+        let weight = request_weight(&method, &path, &query);
+
+        Ok(PreparedRequest {
+            method,
+            path,
+            security,
+            query,
+            weight,
+        })
+    }
+
+    /// Build HTTP request, signed anew on every attempt.
+    ///
+    /// USER_STREAM endpoints get the `X-MBX-APIKEY` header.
+    /// TRADE and USER_DATA endpoints additionally get `timestamp`, `recvWindow` and `signature` parameters,
+    /// in the query string for GET and DELETE and in the body for POST and PUT.
+    fn build(&self, request: &PreparedRequest) -> Result<HttpRequest, Error> {
+        let PreparedRequest {
+            method,
+            path,
+            security,
+            query,
+            ..
+        } = request;
+
+        let mut query = query.clone();
+        if matches!(security, SecurityType::Trade | SecurityType::UserData) {
+            query = self.sign(query)?;
+        }
+
+        let in_body = matches!(*method, Method::POST | Method::PUT);
+        let url = if query.is_empty() || in_body {
+            format!("{}{}", self.base_url, path)
+        } else {
+            format!("{}{}?{query}", self.base_url, path)
+        };
+
+        let mut headers = HeaderMap::new();
+        if !matches!(security, SecurityType::None) {
+            let api_key = self
+                .cfg
+                .api_key
+                .as_ref()
+                .ok_or("api_key is required for this endpoint")?;
+            let api_key = HeaderValue::from_str(api_key.expose())
+                .map_err(|_| "api_key is not a valid header value")?;
+            headers.insert(HEADER_X_MBX_APIKEY, api_key);
+        }
+
+        let mut body = None;
+        if in_body && !query.is_empty() {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static(CONTENT_TYPE_FORM));
+            body = Some(query);
+        }
+
+        Ok(HttpRequest {
+            method: method.clone(),
+            url,
+            headers,
+            body,
+        })
+    }
+
+    /// Append `timestamp`, `recvWindow` and `signature` to the query.
+    fn sign(&self, query: String) -> Result<String, Error> {
+        let signer = self
+            .signer
+            .as_ref()
+            .ok_or("api_secret is required for this endpoint")?;
+
+        let mut payload = query;
+        if !payload.is_empty() {
+            payload.push('&');
         }
+        payload.push_str(&format!("timestamp={}", timestamp()));
+        if let Some(recv_window) = self.cfg.recv_window {
+            payload.push_str(&format!("&recvWindow={recv_window}"));
+        }
+
+        let signature = serde_urlencoded::to_string([("signature", signer.sign(&payload))])?;
+        Ok(format!("{payload}&{signature}"))
     }
+
+    /// Wait for or reject a request that does not fit into the rate limits.
+    async fn throttle(&self, weight: u64) -> Result<(), Error> {
+        loop {
+            let result = {
+                let mut limiter = self.limiter.lock().unwrap_or_else(PoisonError::into_inner);
+                match limiter.as_mut() {
+                    Some(limiter) => limiter
+                        .try_acquire_at(weight, timestamp())
+                        .map_err(|wait| (limiter.mode(), Duration::from_millis(wait))),
+                    None => Ok(()),
+                }
+            };
+
+            match result {
+                Ok(()) => return Ok(()),
+                Err((ThrottleMode::Reject, wait)) => return Err(Error::Throttled { wait }),
+                Err((ThrottleMode::Delay, wait)) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    async fn send<T>(&self, request: PreparedRequest) -> Result<Response<T>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.send_accepting(request, &[]).await
+    }
+
+    /// Send request and decode the body as the result even if Binance rejected the request
+    /// with one of the `accepted` codes. Used by endpoints that attach data to errors.
+    ///
+    /// Failed requests are retried according to `ClientConfig::retry`.
+    async fn send_accepting<T>(
+        &self,
+        request: PreparedRequest,
+        accepted: &[ErrorCode],
+    ) -> Result<Response<T>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let idempotent = request.method == Method::GET;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+
+            let (error, retry_after) = match self.execute(&request).await {
+                Ok((status, headers, json)) => {
+                    if (200..300).contains(&status) {
+                        return decode(&json, headers, attempts);
+                    }
+                    let error = parse_error(status, json.clone());
+                    if matches!(error, Error::Api { code, .. } if accepted.contains(&code)) {
+                        return decode(&json, headers, attempts);
+                    }
+                    (error, headers.retry_after)
+                }
+                Err(error) => (error, None),
+            };
+
+            match self
+                .cfg
+                .retry
+                .delay(attempts, idempotent, &error, retry_after)
+            {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(error),
+            }
+        }
+    }
+
+    /// Make a single attempt and record rate limit usage from the response headers.
+    async fn execute(&self, request: &PreparedRequest) -> Result<(u16, Headers, String), Error> {
+        self.throttle(request.weight).await?;
+
+        let response = self.transport.send(self.build(request)?).await?;
+        let headers = parse_headers(&response.headers);
+
+        self.usage
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .update(&headers);
+        if let Some(limiter) = self
+            .limiter
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+        {
+            limiter.sync_at(&headers.used_weight, timestamp());
+        }
+
+        Ok((response.status, headers, response.body))
+    }
+}
+
+fn decode<T>(json: &str, headers: Headers, attempts: u32) -> Result<Response<T>, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let result = deserialize_str(json)?;
+    let response = Response {
+        result,
+        headers,
+        attempts,
+    };
+    Ok(response)
 }
 
 // General.
 impl Client {
     /// Test connectivity to the Rest API.
     pub async fn test_connectivity(&self) -> Result<Response<TestConnectivity>, Error> {
-        let url = format!("{}{}", self.base_url, Path::Time);
-
-        let client = reqwest::Client::builder().build()?;
-        let request = client.request(Method::GET, url);
+        let request = self.request(Method::GET, Path::Ping, SecurityType::None, &())?;
 
-        let response = send(request).await?;
+        let response = self.send(request).await?;
         Ok(response)
     }
 
     pub async fn get_server_time(&self) -> Result<Response<ServerTime>, Error> {
-        let url = format!("{}{}", self.base_url, Path::Time);
+        let request = self.request(Method::GET, Path::Time, SecurityType::None, &())?;
 
-        let client = reqwest::Client::builder().build()?;
-        let request = client.request(Method::GET, url);
-
-        let response = send(request).await?;
+        let response = self.send(request).await?;
         Ok(response)
     }
 
@@ -59,13 +411,9 @@ impl Client {
         &self,
         params: GetExchangeInfoParams,
     ) -> Result<Response<ExchangeInfo>, Error> {
-        let query = serde_urlencoded::to_string(&params)?;
-        let url = format!("{}{}?{query}", self.base_url, Path::ExchangeInfo);
-
-        let client = reqwest::Client::builder().build()?;
-        let request = client.request(Method::GET, url);
+        let request = self.request(Method::GET, Path::ExchangeInfo, SecurityType::None, &params)?;
 
-        let response = send(request).await?;
+        let response = self.send(request).await?;
         Ok(response)
     }
 }
@@ -76,13 +424,9 @@ impl Client {
         &self,
         params: GetOrderBookParams,
     ) -> Result<Response<OrderBook>, Error> {
-        let query = serde_urlencoded::to_string(&params)?;
-        let url = format!("{}{}?{query}", self.base_url, Path::ExchangeInfo);
+        let request = self.request(Method::GET, Path::Depth, SecurityType::None, &params)?;
 
-        let client = reqwest::Client::builder().build()?;
-        let request = client.request(Method::GET, url);
-
-        let response = send(request).await?;
+        let response = self.send(request).await?;
         Ok(response)
     }
 
@@ -91,13 +435,9 @@ impl Client {
         &self,
         params: GetRecentTradesParams,
     ) -> Result<Response<Vec<RecentTrade>>, Error> {
-        let query = serde_urlencoded::to_string(&params)?;
-        let url = format!("{}{}?{query}", self.base_url, Path::Trades);
-
-        let client = reqwest::Client::builder().build()?;
-        let request = client.request(Method::GET, url);
+        let request = self.request(Method::GET, Path::Trades, SecurityType::None, &params)?;
 
-        let response = send(request).await?;
+        let response = self.send(request).await?;
         Ok(response)
     }
 
@@ -106,13 +446,14 @@ impl Client {
         &self,
         params: GetOlderTradesParams,
     ) -> Result<Response<Vec<RecentTrade>>, Error> {
-        let query = serde_urlencoded::to_string(&params)?;
-        let url = format!("{}{}?{query}", self.base_url, Path::HistoricalTrades);
+        let request = self.request(
+            Method::GET,
+            Path::HistoricalTrades,
+            SecurityType::None,
+            &params,
+        )?;
 
-        let client = reqwest::Client::builder().build()?;
-        let request = client.request(Method::GET, url);
-
-        let response = send(request).await?;
+        let response = self.send(request).await?;
         Ok(response)
     }
 
@@ -124,13 +465,9 @@ impl Client {
         &self,
         params: GetAggregateTradesParams,
     ) -> Result<Response<Vec<AggregateTrade>>, Error> {
-        let query = serde_urlencoded::to_string(&params)?;
-        let url = format!("{}{}?{query}", self.base_url, Path::AggTrades);
-
-        let client = reqwest::Client::builder().build()?;
-        let request = client.request(Method::GET, url);
+        let request = self.request(Method::GET, Path::AggTrades, SecurityType::None, &params)?;
 
-        let response = send(request).await?;
+        let response = self.send(request).await?;
         Ok(response)
     }
 
@@ -147,13 +484,9 @@ impl Client {
         &self,
         params: GetKlineListParams,
     ) -> Result<Response<Vec<Kline>>, Error> {
-        let query = serde_urlencoded::to_string(&params)?;
-        let url = format!("{}{}?{query}", self.base_url, Path::KLines);
+        let request = self.request(Method::GET, Path::KLines, SecurityType::None, &params)?;
 
-        let client = reqwest::Client::builder().build()?;
-        let request = client.request(Method::GET, url);
-
-        let response = send(request).await?;
+        let response = self.send(request).await?;
         Ok(response)
     }
 
@@ -173,13 +506,9 @@ impl Client {
         &self,
         params: GetKlineListParams,
     ) -> Result<Response<Vec<Kline>>, Error> {
-        let query = serde_urlencoded::to_string(&params)?;
-        let url = format!("{}{}?{query}", self.base_url, Path::UIKLines);
-
-        let client = reqwest::Client::builder().build()?;
-        let request = client.request(Method::GET, url);
+        let request = self.request(Method::GET, Path::UIKLines, SecurityType::None, &params)?;
 
-        let response = send(request).await?;
+        let response = self.send(request).await?;
         Ok(response)
     }
 
@@ -188,13 +517,9 @@ impl Client {
         &self,
         params: GetCurrentAveragePriceParams,
     ) -> Result<Response<CurrentAveragePrice>, Error> {
-        let query = serde_urlencoded::to_string(&params)?;
-        let url = format!("{}{}?{query}", self.base_url, Path::AvgPrice);
+        let request = self.request(Method::GET, Path::AvgPrice, SecurityType::None, &params)?;
 
-        let client = reqwest::Client::builder().build()?;
-        let request = client.request(Method::GET, url);
-
-        let response = send(request).await?;
+        let response = self.send(request).await?;
         Ok(response)
     }
 
@@ -203,36 +528,514 @@ impl Client {
         &self,
         params: GetTickerPriceChangeStatisticsParams,
     ) -> Result<Response<TickerPriceChangeStatistic>, Error> {
-        let query = serde_urlencoded::to_string(&params)?;
-        let url = format!("{}{}?{query}", self.base_url, Path::Ticker24hr);
+        let request = self.request(Method::GET, Path::Ticker24hr, SecurityType::None, &params)?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+}
+
+// Trading.
+impl Client {
+    /// Send in a new order.
+    ///
+    /// Response type depends on `newOrderRespType`.
+    pub async fn new_order(
+        &self,
+        params: NewOrderParams,
+    ) -> Result<Response<OrderResponse>, Error> {
+        let request = self.request(Method::POST, Path::Order, SecurityType::Trade, &params)?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Test new order creation and signature/recvWindow.
+    /// Creates and validates a new order but does not send it into the matching engine.
+    pub async fn test_new_order(
+        &self,
+        params: TestNewOrderParams,
+    ) -> Result<Response<TestNewOrder>, Error> {
+        let request = self.request(Method::POST, Path::OrderTest, SecurityType::Trade, &params)?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Check an order's status.
+    pub async fn query_order(&self, params: QueryOrderParams) -> Result<Response<Order>, Error> {
+        let request = self.request(Method::GET, Path::Order, SecurityType::UserData, &params)?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Cancel an active order.
+    pub async fn cancel_order(
+        &self,
+        params: CancelOrderParams,
+    ) -> Result<Response<CanceledOrder>, Error> {
+        let request = self.request(Method::DELETE, Path::Order, SecurityType::Trade, &params)?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Cancels all active orders on a symbol.
+    /// This includes orders that are part of an order list.
+    pub async fn cancel_all_open_orders(
+        &self,
+        params: CancelAllOpenOrdersParams,
+    ) -> Result<Response<Vec<CanceledOpenOrder>>, Error> {
+        let request = self.request(
+            Method::DELETE,
+            Path::OpenOrders,
+            SecurityType::Trade,
+            &params,
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Get all open orders on a symbol. Careful when accessing this with no symbol.
+    pub async fn current_open_orders(
+        &self,
+        params: GetOpenOrdersParams,
+    ) -> Result<Response<Vec<Order>>, Error> {
+        let request = self.request(
+            Method::GET,
+            Path::OpenOrders,
+            SecurityType::UserData,
+            &params,
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
 
-        let client = reqwest::Client::builder().build()?;
-        let request = client.request(Method::GET, url);
+    /// Cancels an existing order and places a new order on the same symbol.
+    ///
+    /// A half-done operation (-2021 Order cancel-replace partially failed) is reported
+    /// as `CancelReplaceOrder::NewOrderFailed` or `CancelReplaceOrder::CancelFailed`.
+    pub async fn cancel_replace_order(
+        &self,
+        params: CancelReplaceOrderParams,
+    ) -> Result<Response<CancelReplaceOrder>, Error> {
+        let request = self.request(
+            Method::POST,
+            Path::OrderCancelReplace,
+            SecurityType::Trade,
+            &params,
+        )?;
 
-        let response = send(request).await?;
+        let accepted = [
+            ErrorCode::OrderCancelReplacePartiallyFailed,
+            ErrorCode::OrderCancelReplaceFailed,
+        ];
+        let response = self.send_accepting(request, &accepted).await?;
+        Ok(response)
+    }
+
+    /// Reduce the quantity of an existing open order.
+    /// This adds 0 orders to the unfilled order count.
+    pub async fn amend_order_keep_priority(
+        &self,
+        params: AmendOrderKeepPriorityParams,
+    ) -> Result<Response<AmendOrderKeepPriority>, Error> {
+        let request = self.request(
+            Method::PUT,
+            Path::OrderAmendKeepPriority,
+            SecurityType::Trade,
+            &params,
+        )?;
+
+        let response = self.send(request).await?;
         Ok(response)
     }
 }
 
-async fn send<T>(request: RequestBuilder) -> Result<Response<T>, Error>
-where
-    T: serde::de::DeserializeOwned,
-{
-    let response = request.send().await?;
-    let headers = parse_headers(&response.headers());
-    let json = response.text().await?;
+// Order lists.
+impl Client {
+    /// Send in an one-cancels-the-other (OCO) pair, where activation of one order immediately cancels the other.
+    pub async fn new_order_list_oco(
+        &self,
+        params: NewOrderListOCOParams,
+    ) -> Result<Response<OrderListResponse>, Error> {
+        let request = self.request(
+            Method::POST,
+            Path::OrderListOCO,
+            SecurityType::Trade,
+            &params,
+        )?;
 
-    let result = deserialize_str(&json)?;
-    let response = Response { result, headers };
-    Ok(response)
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Places an OTO.
+    /// The pending order is placed only when the working order is fully filled.
+    pub async fn new_order_list_oto(
+        &self,
+        params: NewOrderListOTOParams,
+    ) -> Result<Response<OrderListResponse>, Error> {
+        let request = self.request(
+            Method::POST,
+            Path::OrderListOTO,
+            SecurityType::Trade,
+            &params,
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Place an OTOCO.
+    /// The pending orders are placed as an OCO only when the working order is fully filled.
+    pub async fn new_order_list_otoco(
+        &self,
+        params: NewOrderListOTOCOParams,
+    ) -> Result<Response<OrderListResponse>, Error> {
+        let request = self.request(
+            Method::POST,
+            Path::OrderListOTOCO,
+            SecurityType::Trade,
+            &params,
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Cancel an entire order list.
+    pub async fn cancel_order_list(
+        &self,
+        params: CancelOrderListParams,
+    ) -> Result<Response<CanceledOrderList>, Error> {
+        let request = self.request(
+            Method::DELETE,
+            Path::OrderList,
+            SecurityType::Trade,
+            &params,
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Retrieves a specific order list based on provided optional parameters.
+    pub async fn query_order_list(
+        &self,
+        params: QueryOrderListParams,
+    ) -> Result<Response<OrderList>, Error> {
+        let request = self.request(
+            Method::GET,
+            Path::OrderList,
+            SecurityType::UserData,
+            &params,
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Query open order lists.
+    pub async fn query_open_order_lists(&self) -> Result<Response<Vec<OrderList>>, Error> {
+        let request = self.request(
+            Method::GET,
+            Path::OpenOrderList,
+            SecurityType::UserData,
+            &(),
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+}
+
+// Smart Order Routing (SOR).
+impl Client {
+    /// Places an order using smart order routing (SOR).
+    ///
+    /// Eligible symbols and routing groups are listed in `ExchangeInfo::sors`.
+    pub async fn new_sor_order(
+        &self,
+        params: NewSOROrderParams,
+    ) -> Result<Response<OrderResponse>, Error> {
+        let request = self.request(Method::POST, Path::SOROrder, SecurityType::Trade, &params)?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Test new order creation and signature/recvWindow using smart order routing (SOR).
+    /// Creates and validates a new order but does not send it into the matching engine.
+    pub async fn test_sor_order(
+        &self,
+        params: TestSOROrderParams,
+    ) -> Result<Response<TestNewOrder>, Error> {
+        let request = self.request(
+            Method::POST,
+            Path::SOROrderTest,
+            SecurityType::Trade,
+            &params,
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
 }
 
-/// Parse response headers: Retry-After
+// Account.
+impl Client {
+    /// Displays the user's unfilled order count for all intervals.
+    pub async fn get_unfilled_order_count(
+        &self,
+    ) -> Result<Response<Vec<UnfilledOrderCount>>, Error> {
+        let request = self.request(
+            Method::GET,
+            Path::RateLimitOrder,
+            SecurityType::UserData,
+            &(),
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Get current account information.
+    pub async fn get_account_information(
+        &self,
+        params: GetAccountInformationParams,
+    ) -> Result<Response<AccountInformation>, Error> {
+        let request = self.request(Method::GET, Path::Account, SecurityType::UserData, &params)?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Retrieves allocations resulting from SOR order placement.
+    pub async fn get_allocations(
+        &self,
+        params: GetAllocationsParams,
+    ) -> Result<Response<Vec<Allocation>>, Error> {
+        let request = self.request(
+            Method::GET,
+            Path::MyAllocations,
+            SecurityType::UserData,
+            &params,
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+}
+
+// User data stream.
+impl Client {
+    /// Start a new user data stream. The listen key is valid for 60 minutes,
+    /// an existing valid key is returned and extended if there is one.
+    pub async fn start_user_data_stream(&self) -> Result<Response<ListenKey>, Error> {
+        let request = self.request(
+            Method::POST,
+            Path::UserDataStream,
+            SecurityType::UserStream,
+            &(),
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Extend the listen key validity to 60 minutes from now.
+    /// Binance recommends sending a keepalive every 30 minutes.
+    pub async fn keepalive_user_data_stream(
+        &self,
+        params: ListenKeyParams,
+    ) -> Result<Response<KeepaliveUserDataStream>, Error> {
+        let request = self.request(
+            Method::PUT,
+            Path::UserDataStream,
+            SecurityType::UserStream,
+            &params,
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+
+    /// Close out a user data stream.
+    pub async fn close_user_data_stream(
+        &self,
+        params: ListenKeyParams,
+    ) -> Result<Response<CloseUserDataStream>, Error> {
+        let request = self.request(
+            Method::DELETE,
+            Path::UserDataStream,
+            SecurityType::UserStream,
+            &params,
+        )?;
+
+        let response = self.send(request).await?;
+        Ok(response)
+    }
+}
+
+/// Parse Binance error body: {"code":-1121,"msg":"Invalid symbol."}
+fn parse_error(status: u16, body: String) -> Error {
+    match serde_json::from_str::<ApiError>(&body) {
+        Ok(ApiError { code, msg }) => Error::Api { status, code, msg },
+        Err(_) => Error::Http { status, body },
+    }
+}
+
+/// Parse response headers: Retry-After, X-MBX-USED-WEIGHT-*, X-MBX-ORDER-COUNT-*
 fn parse_headers(headers: &HeaderMap) -> Headers {
     let retry_after = headers
         .get(HEADER_RETRY_AFTER)
-        .map(|h| h.to_str().unwrap_or_default().parse().ok())
-        .flatten();
+        .and_then(|h| h.to_str().unwrap_or_default().parse().ok());
 
-    Headers { retry_after }
+    let mut used_weight = BTreeMap::new();
+    let mut order_count = BTreeMap::new();
+    for (name, value) in headers {
+        let name = name.as_str();
+        let (counters, window) = if let Some(window) =
+            strip_prefix_ignore_case(name, HEADER_X_MBX_USED_WEIGHT_PREFIX)
+        {
+            (&mut used_weight, window)
+        } else if let Some(window) = strip_prefix_ignore_case(name, HEADER_X_MBX_ORDER_COUNT_PREFIX)
+        {
+            (&mut order_count, window)
+        } else {
+            continue;
+        };
+
+        let window = window.parse::<RateLimitWindow>();
+        let value = value.to_str().unwrap_or_default().parse::<u64>();
+        if let (Ok(window), Ok(value)) = (window, value) {
+            counters.insert(window, value);
+        }
+    }
+
+    Headers {
+        retry_after,
+        used_weight,
+        order_count,
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &s[prefix.len()..])
+}
+
+/// Current time in milliseconds since UNIX epoch.
+pub(crate) fn timestamp() -> Timestamp {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as Timestamp)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spot::{CannedTransport, HttpResponse, RateLimitInterval};
+
+    use super::*;
+
+    #[test]
+    fn parse_error_api() {
+        let error = parse_error(
+            400,
+            String::from(r#"{"code":-1121,"msg":"Invalid symbol."}"#),
+        );
+
+        assert!(matches!(
+            error,
+            Error::Api { status: 400, code: ErrorCode::BadSymbol, ref msg } if msg == "Invalid symbol."
+        ));
+    }
+
+    #[test]
+    fn parse_error_http() {
+        let error = parse_error(502, String::from("<html>Bad Gateway</html>"));
+
+        assert!(matches!(error, Error::Http { status: 502, .. }));
+        assert_eq!(None, error.code());
+        assert_eq!(Some(502), error.status());
+    }
+
+    #[test]
+    fn http_client_options() {
+        let cfg = ClientConfig {
+            connect_timeout: Some(Duration::from_secs(1)),
+            read_timeout: Some(Duration::from_secs(5)),
+            proxy: Some(String::from("http://127.0.0.1:3128")),
+            user_agent: Some(String::from("mm/1.0")),
+            tcp_keepalive: Some(Duration::from_secs(30)),
+            local_address: Some(IpAddr::from([127, 0, 0, 1])),
+            ..Default::default()
+        };
+        assert!(Client::new(cfg).is_ok());
+
+        let cfg = ClientConfig {
+            proxy: Some(String::from("not a url")),
+            ..Default::default()
+        };
+        assert!(matches!(Client::new(cfg), Err(Error::Reqwest(_))));
+    }
+
+    #[tokio::test]
+    async fn retry_with_canned_transport() {
+        let transport = Arc::new(CannedTransport::new());
+        let busy = r#"{"code":-1008,"msg":"Server is currently overloaded with other requests."}"#;
+        transport.push(Method::GET, "/api/v3/time", HttpResponse::json(503, busy));
+        transport.push(
+            Method::GET,
+            "/api/v3/time",
+            HttpResponse::json(200, r#"{"serverTime":1499827319559}"#),
+        );
+        let cfg = ClientConfig {
+            retry: RetryPolicy {
+                initial_backoff: Duration::from_millis(1),
+                ..Default::default()
+            },
+            transport: Some(transport.clone()),
+            ..Default::default()
+        };
+        let client = Client::new(cfg).unwrap();
+
+        let response = client.get_server_time().await.unwrap();
+
+        assert_eq!(response.result.server_time, 1499827319559);
+        assert_eq!(response.attempts, 2);
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[test]
+    fn parse_headers_rate_limits() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", "30".parse().unwrap());
+        headers.insert("x-mbx-used-weight-1m", "10".parse().unwrap());
+        headers.insert("x-mbx-order-count-10s", "1".parse().unwrap());
+        headers.insert("x-mbx-order-count-1d", "5".parse().unwrap());
+        headers.insert("x-mbx-uuid", "f9cbc7e9".parse().unwrap());
+        let window = |interval, interval_num| RateLimitWindow {
+            interval,
+            interval_num,
+        };
+        let expected = Headers {
+            retry_after: Some(30),
+            used_weight: BTreeMap::from([(window(RateLimitInterval::Minute, 1), 10)]),
+            order_count: BTreeMap::from([
+                (window(RateLimitInterval::Second, 10), 1),
+                (window(RateLimitInterval::Day, 1), 5),
+            ]),
+        };
+
+        let current = parse_headers(&headers);
+
+        assert_eq!(expected, current);
+    }
 }
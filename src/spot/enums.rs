@@ -2,11 +2,10 @@
 //!
 //! This will apply for both REST API and WebSocket API.
 
-use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 /// Symbol status.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SymbolStatus {
     Trading,
@@ -16,7 +15,7 @@ pub enum SymbolStatus {
 }
 
 /// Order status.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderStatus {
     /// The order has been accepted by the engine.
@@ -40,6 +39,26 @@ pub enum OrderStatus {
     ExpiredInMatch,
 }
 
+/// Execution type of an order update in the user data stream.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExecutionType {
+    /// The order has been accepted into the engine.
+    New,
+    /// The order has been canceled by the user.
+    Canceled,
+    /// Currently unused.
+    Replaced,
+    /// The order has been rejected and was not processed.
+    Rejected,
+    /// Part of the order or all of the order's quantity has filled.
+    Trade,
+    /// The order was canceled according to the order type's rules or by the exchange.
+    Expired,
+    /// The order has expired due to STP.
+    TradePrevention,
+}
+
 /// Order List Status.
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -79,9 +98,17 @@ pub enum AllocationType {
     SOR,
 }
 
-/// Order types.
+/// Match type.
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MatchType {
+    /// The trade was allocated to the order by SOR.
+    OnePartyTradeReport,
+}
+
+/// Order types.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderType {
     Limit,
     Market,
@@ -108,7 +135,7 @@ pub enum WorkingFloor {
 }
 
 /// Order side.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
 pub enum OrderSide {
     BUY,
     SELL,
@@ -116,7 +143,7 @@ pub enum OrderSide {
 
 /// Time in force.
 /// This sets how long an order will be active before expiration.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
 pub enum TimeInForce {
     /// Good Til Canceled
     /// An order will be on the book unless the order is canceled.
@@ -129,9 +156,48 @@ pub enum TimeInForce {
     FOK,
 }
 
-/// Rate limiter.
+/// Cancel restrictions.
+/// Supported values: ONLY_NEW - Cancel will succeed if the order status is NEW.
+/// ONLY_PARTIALLY_FILLED - Cancel will succeed if order status is PARTIALLY_FILLED.
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CancelRestrictions {
+    OnlyNew,
+    OnlyPartiallyFilled,
+}
+
+/// Cancel replace mode.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CancelReplaceMode {
+    /// If the cancel request fails, the new order placement will not be attempted.
+    StopOnFailure,
+    /// New order placement will be attempted even if cancel request fails.
+    AllowFailure,
+}
+
+/// Result of one half of a cancel-replace operation.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CancelReplaceStatus {
+    Success,
+    Failure,
+    NotAttempted,
+}
+
+/// Order rate limit exceeded mode.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderRateLimitExceededMode {
+    /// Only attempts to cancel the order if account has not exceeded the order rate limit.
+    DoNothing,
+    /// Will always cancel the order.
+    CancelOnly,
+}
+
+/// Rate limiter.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RateLimiter {
     RequestWeight,
     Orders,
@@ -139,15 +205,28 @@ pub enum RateLimiter {
 }
 
 /// Rate limit interval.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RateLimitInterval {
     Second,
     Minute,
+    Hour,
     Day,
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+impl RateLimitInterval {
+    /// Interval duration in milliseconds.
+    pub fn millis(&self) -> u64 {
+        match self {
+            Self::Second => 1_000,
+            Self::Minute => 60_000,
+            Self::Hour => 3_600_000,
+            Self::Day => 86_400_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub enum KlineInterval {
     #[serde(rename = "1s")]
     Second1,
@@ -208,7 +287,7 @@ impl std::fmt::Display for KlineInterval {
 }
 
 /// Self trade prevention (STP) Mode.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum STPMode {
     None,
@@ -231,10 +310,21 @@ pub enum SecurityType {
     UserStream,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(tag = "filterType", rename_all = "SCREAMING_SNAKE_CASE")]
+/// Exchange filter, applies to all symbols of the account.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(
+    tag = "filterType",
+    rename_all = "SCREAMING_SNAKE_CASE",
+    rename_all_fields = "camelCase"
+)]
 pub enum ExchangeFilter {
-    PriceFilter { tick_size: Decimal },
-    LotSize { step_size: Decimal },
-    // TODO:
+    /// Maximum open orders of the account, including order lists.
+    ExchangeMaxNumOrders { max_num_orders: u64 },
+    /// Maximum open STOP_LOSS, STOP_LOSS_LIMIT, TAKE_PROFIT and TAKE_PROFIT_LIMIT orders of the account.
+    ExchangeMaxNumAlgoOrders { max_num_algo_orders: u64 },
+    /// Maximum open iceberg orders of the account.
+    ExchangeMaxNumIcebergOrders { max_num_iceberg_orders: u64 },
+    /// Filter not known to this version of the SDK.
+    #[serde(other)]
+    Unknown,
 }
@@ -1,9 +1,38 @@
-use serde_repr::{Deserialize_repr, Serialize_repr};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-/// Error codes for Binance.
-#[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq)]
-#[repr(i16)]
-pub enum ErrorCode {
+/// Declare `ErrorCode` with a numeric code for every variant
+/// plus `Unrecognized` for codes not known to this crate.
+macro_rules! error_codes {
+    ($($(#[$meta:meta])* $name:ident = $code:literal,)*) => {
+        /// Error codes for Binance.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $($(#[$meta])* $name,)*
+            /// Code not known to this crate.
+            Unrecognized(i16),
+        }
+
+        impl ErrorCode {
+            pub fn code(&self) -> i16 {
+                match self {
+                    $(Self::$name => $code,)*
+                    Self::Unrecognized(code) => *code,
+                }
+            }
+        }
+
+        impl From<i16> for ErrorCode {
+            fn from(code: i16) -> Self {
+                match code {
+                    $($code => Self::$name,)*
+                    code => Self::Unrecognized(code),
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
     // 10xx - General Server or Network issues
     /// An unknown error occurred while processing the request.
     Unknown = -1000,
@@ -237,19 +266,79 @@ pub enum ErrorCode {
     ClientOrderIdInvalid = -2039,
 }
 
+impl Serialize for ErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i16(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        i16::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unrecognized(code) => write!(f, "{code}"),
+            known => write!(f, "{} ({known:?})", known.code()),
+        }
+    }
+}
+
+/// Error returned by Binance in place of a result.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ApiError {
+    pub code: ErrorCode,
+    pub msg: String,
+}
+
 #[derive(Debug)]
 pub enum Error {
+    /// Binance rejected the request and explained why.
+    Api {
+        status: u16,
+        code: ErrorCode,
+        msg: String,
+    },
+    /// Unsuccessful HTTP status without a Binance error body (e.g. WAF or gateway errors).
+    Http {
+        status: u16,
+        body: String,
+    },
+    /// The request was not sent because it would exceed client-side rate limits.
+    Throttled {
+        wait: std::time::Duration,
+    },
+    /// A stream control request (e.g. `SUBSCRIBE`) was rejected.
+    StreamRequest {
+        code: i64,
+        msg: String,
+    },
     Io(std::io::Error),
     Msg(String),
     Reqwest(reqwest::Error),
     SerdeJson(serde_json::Error),
     SerdeUrlEncoded(serde_urlencoded::ser::Error),
     SerdePathToError(serde_path_to_error::Error<serde_json::Error>),
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Error::Api { status, code, msg } => {
+                write!(
+                    f,
+                    "Binance API error: status: {status}, code: {code}, msg: {msg}"
+                )
+            }
+            Error::Http { status, body } => write!(f, "HTTP error: status: {status}, body: {body}"),
+            Error::Throttled { wait } => write!(f, "throttled: retry in {wait:?}"),
+            Error::StreamRequest { code, msg } => {
+                write!(f, "stream request error: code: {code}, msg: {msg}")
+            }
             Error::Io(error) => write!(f, "I/O error: {error}"),
             Error::Msg(msg) => write!(f, "{msg}"),
             Error::Reqwest(error) => write!(f, "reqwest error: {error}"),
@@ -261,12 +350,32 @@ impl std::fmt::Display for Error {
                 error.path(),
                 error.inner()
             ),
+            Error::WebSocket(error) => write!(f, "WebSocket error: {error}"),
         }
     }
 }
 
 impl std::error::Error for Error {}
 
+impl Error {
+    /// Binance error code, if the request was rejected by Binance.
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            Error::Api { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// HTTP status code, if a response was received.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::Api { status, .. } | Error::Http { status, .. } => Some(*status),
+            Error::Reqwest(error) => error.status().map(|s| s.as_u16()),
+            _ => None,
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
         Error::Io(err)
@@ -308,3 +417,43 @@ impl From<serde_path_to_error::Error<serde_json::Error>> for Error {
         Error::SerdePathToError(err)
     }
 }
+
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        Error::WebSocket(Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spot::serde::deserialize_str;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_api_error() {
+        let json = r#"{"code":-1121,"msg":"Invalid symbol."}"#;
+        let expected = ApiError {
+            code: ErrorCode::BadSymbol,
+            msg: String::from("Invalid symbol."),
+        };
+
+        let current = deserialize_str(json).unwrap();
+
+        assert_eq!(expected, current);
+    }
+
+    #[test]
+    fn deserialize_api_error_unrecognized_code() {
+        let json = r#"{"code":-9999,"msg":"Something new."}"#;
+        let expected = ApiError {
+            code: ErrorCode::Unrecognized(-9999),
+            msg: String::from("Something new."),
+        };
+
+        let current: ApiError = deserialize_str(json).unwrap();
+
+        assert_eq!(expected, current);
+        assert_eq!(-9999, current.code.code());
+    }
+}
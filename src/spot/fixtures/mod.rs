@@ -0,0 +1,751 @@
+//! Recorded responses of every REST endpoint, served to the [`Client`] through a [`CannedTransport`].
+//!
+//! Each test checks the method, path and parameters of the request and the decoded response.
+
+use std::{path::PathBuf, sync::Arc};
+
+use reqwest::Method;
+use rust_decimal::dec;
+
+use crate::spot::{
+    AmendOrderKeepPriorityParams, CancelAllOpenOrdersParams, CancelOrderListParams,
+    CancelOrderParams, CancelReplaceMode, CancelReplaceOrder, CancelReplaceOrderParams,
+    CanceledOpenOrder, CannedTransport, Client, ClientConfig, ContingencyType, ExchangeFilter,
+    Filter, GetAccountInformationParams, GetAggregateTradesParams, GetAllocationsParams,
+    GetCurrentAveragePriceParams, GetExchangeInfoParams, GetKlineListParams, GetOlderTradesParams,
+    GetOpenOrdersParams, GetOrderBookParams, GetRecentTradesParams,
+    GetTickerPriceChangeStatisticsParams, HEADER_X_MBX_APIKEY, HttpResponse, KlineInterval,
+    ListenKeyParams, NewOrderListOCOParams, NewOrderListOTOCOParams, NewOrderListOTOParams,
+    NewOrderParams, NewSOROrderParams, OrderListStatus, OrderResponse, OrderSide, OrderStatus,
+    OrderType, Path, QueryOrderListParams, QueryOrderParams, RateLimitInterval, SecurityType,
+    SymbolOrSymbols, TestNewOrderParams, TestSOROrderParams, TickerPriceChangeStatistic,
+    TimeInForce, WorkingFloor,
+};
+
+fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/spot/fixtures")
+        .join(name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+}
+
+/// Client answering `method` requests to `path` with the recorded response.
+fn canned(method: Method, path: Path, name: &str) -> (Client, Arc<CannedTransport>) {
+    let transport = Arc::new(CannedTransport::new());
+    transport.push(
+        method,
+        &path.to_string(),
+        HttpResponse::json(200, fixture(name)),
+    );
+    let cfg = ClientConfig {
+        api_key: Some("api-key".into()),
+        api_secret: Some("api-secret".into()),
+        transport: Some(transport.clone()),
+        ..Default::default()
+    };
+    (Client::new(cfg).unwrap(), transport)
+}
+
+/// Assert the only request sent, `params` without `timestamp` and `signature`.
+fn assert_request(
+    transport: &CannedTransport,
+    method: Method,
+    path: Path,
+    security: SecurityType,
+    params: &str,
+) {
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method, method);
+
+    let url = request.url.parse::<reqwest::Url>().unwrap();
+    assert_eq!(url.path(), path.to_string());
+    let sent = match method {
+        Method::POST | Method::PUT => request.body.clone().unwrap_or_default(),
+        _ => url.query().unwrap_or_default().to_string(),
+    };
+    let api_key = request.headers.get(HEADER_X_MBX_APIKEY);
+    assert_eq!(
+        api_key.is_some(),
+        security != SecurityType::None,
+        "X-MBX-APIKEY of {path}"
+    );
+
+    let mut pairs: Vec<&str> = sent.split('&').filter(|pair| !pair.is_empty()).collect();
+    if matches!(security, SecurityType::Trade | SecurityType::UserData) {
+        let signature = pairs.pop().unwrap();
+        let timestamp = pairs.pop().unwrap();
+        assert!(signature.starts_with("signature="), "{sent}");
+        assert!(timestamp.starts_with("timestamp="), "{sent}");
+    }
+    assert_eq!(pairs.join("&"), params, "parameters of {path}");
+}
+
+#[tokio::test]
+async fn general() {
+    let (client, transport) = canned(Method::GET, Path::Ping, "empty.json");
+    client.test_connectivity().await.unwrap();
+    assert_request(&transport, Method::GET, Path::Ping, SecurityType::None, "");
+
+    let (client, transport) = canned(Method::GET, Path::Time, "time.json");
+    let response = client.get_server_time().await.unwrap();
+    assert_request(&transport, Method::GET, Path::Time, SecurityType::None, "");
+    assert_eq!(response.result.server_time, 1499827319559);
+
+    let (client, transport) = canned(Method::GET, Path::ExchangeInfo, "exchange_info.json");
+    let params = GetExchangeInfoParams {
+        symbol: None,
+        symbols: Some(vec![String::from("BTCUSDT"), String::from("ETHBTC")]),
+        permissions: None,
+        show_permission_sets: Some(false),
+        symbol_status: None,
+    };
+    let response = client.get_exchange_info(params).await.unwrap();
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::ExchangeInfo,
+        SecurityType::None,
+        "symbols=%5B%22BTCUSDT%22%2C%22ETHBTC%22%5D&showPermissionSets=false",
+    );
+    let info = response.result;
+    assert_eq!(
+        info.exchange_filters,
+        [ExchangeFilter::ExchangeMaxNumOrders {
+            max_num_orders: 1000
+        }]
+    );
+    let symbol = info.symbol("ETHBTC").unwrap();
+    assert_eq!(symbol.filters.len(), 4);
+    assert_eq!(
+        symbol.filters[1],
+        Filter::LotSize {
+            min_qty: dec!(0.0001),
+            max_qty: dec!(100000),
+            step_size: dec!(0.0001),
+        }
+    );
+}
+
+#[tokio::test]
+async fn market_data() {
+    let (client, transport) = canned(Method::GET, Path::Depth, "depth.json");
+    let params = GetOrderBookParams {
+        symbol: String::from("BNBBTC"),
+        limit: Some(5),
+    };
+    let book = client.get_order_book(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::Depth,
+        SecurityType::None,
+        "symbol=BNBBTC&limit=5",
+    );
+    assert_eq!(book.last_update_id, 1027024);
+    assert_eq!(book.bids[0].price(), dec!(4));
+    assert_eq!(book.bids[0].qty(), dec!(431));
+    assert_eq!(book.asks[0].price(), dec!(4.000002));
+    assert_eq!(book.asks[0].qty(), dec!(12));
+
+    let (client, transport) = canned(Method::GET, Path::Trades, "trades.json");
+    let params = GetRecentTradesParams {
+        symbol: String::from("BNBBTC"),
+        limit: Some(1),
+    };
+    let trades = client.recent_trades_list(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::Trades,
+        SecurityType::None,
+        "symbol=BNBBTC&limit=1",
+    );
+    assert_eq!((trades[0].id, trades[0].qty), (28457, dec!(12)));
+
+    let (client, transport) = canned(Method::GET, Path::HistoricalTrades, "trades.json");
+    let params = GetOlderTradesParams {
+        symbol: String::from("BNBBTC"),
+        limit: None,
+        from_id: Some(28457),
+    };
+    let trades = client.old_trade_lookup(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::HistoricalTrades,
+        SecurityType::None,
+        "symbol=BNBBTC&fromId=28457",
+    );
+    assert_eq!(trades[0].price, dec!(4.000001));
+
+    let (client, transport) = canned(Method::GET, Path::AggTrades, "agg_trades.json");
+    let params = GetAggregateTradesParams {
+        symbol: String::from("BNBBTC"),
+        from_id: None,
+        start_time: Some(1498793709000),
+        end_time: Some(1498793710000),
+        limit: None,
+    };
+    let trades = client.aggregate_trades_list(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::AggTrades,
+        SecurityType::None,
+        "symbol=BNBBTC&startTime=1498793709000&endTime=1498793710000",
+    );
+    assert_eq!((trades[0].id, trades[0].qty), (26129, dec!(4.70443515)));
+
+    for path in [Path::KLines, Path::UIKLines] {
+        let (client, transport) = canned(Method::GET, path, "klines.json");
+        let params = GetKlineListParams {
+            symbol: String::from("BNBBTC"),
+            interval: KlineInterval::Hour1,
+            start_time: None,
+            end_time: None,
+            time_zone: Some(String::from("8")),
+            limit: Some(1),
+        };
+        let klines = match path {
+            Path::KLines => client.get_kline_list(params).await,
+            _ => client.get_ui_kline_list(params).await,
+        };
+        let kline = &klines.unwrap().result[0];
+        assert_request(
+            &transport,
+            Method::GET,
+            path,
+            SecurityType::None,
+            "symbol=BNBBTC&interval=1h&timeZone=8&limit=1",
+        );
+        assert_eq!(kline.time_open(), 1499040000000);
+        assert_eq!(kline.close(), dec!(0.015771));
+        assert_eq!(kline.id(), 308);
+    }
+
+    let (client, transport) = canned(Method::GET, Path::AvgPrice, "avg_price.json");
+    let params = GetCurrentAveragePriceParams {
+        symbol: String::from("BNBBTC"),
+    };
+    let price = client.get_current_average_price(params).await.unwrap();
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::AvgPrice,
+        SecurityType::None,
+        "symbol=BNBBTC",
+    );
+    assert_eq!(price.result.price, dec!(9.35751834));
+
+    let (client, transport) = canned(Method::GET, Path::Ticker24hr, "ticker_24hr.json");
+    let params = GetTickerPriceChangeStatisticsParams::Full(SymbolOrSymbols {
+        symbol: Some(String::from("BNBBTC")),
+        symbols: None,
+    });
+    let ticker = client.ticker_price_change_statistics(params).await.unwrap();
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::Ticker24hr,
+        SecurityType::None,
+        "type=FULL&symbol=BNBBTC",
+    );
+    assert!(matches!(
+        ticker.result,
+        TickerPriceChangeStatistic::FullElement(ticker) if ticker.last_price == dec!(4.000002) && ticker.count == 76
+    ));
+}
+
+fn new_order() -> NewOrderParams {
+    NewOrderParams {
+        symbol: String::from("BTCUSDT"),
+        side: OrderSide::SELL,
+        order_type: OrderType::Market,
+        time_in_force: None,
+        quantity: Some(dec!(10)),
+        quote_order_qty: None,
+        price: None,
+        new_client_order_id: None,
+        strategy_id: None,
+        strategy_type: None,
+        stop_price: None,
+        trailing_delta: None,
+        iceberg_qty: None,
+        new_order_resp_type: None,
+        self_trade_prevention_mode: None,
+    }
+}
+
+#[tokio::test]
+async fn trading() {
+    let (client, transport) = canned(Method::POST, Path::Order, "new_order.json");
+    let response = client.new_order(new_order()).await.unwrap();
+    assert_request(
+        &transport,
+        Method::POST,
+        Path::Order,
+        SecurityType::Trade,
+        "symbol=BTCUSDT&side=SELL&type=MARKET&quantity=10",
+    );
+    assert!(matches!(
+        response.result,
+        OrderResponse::Result(order) if order.order_id == 28 && order.status == OrderStatus::Filled
+    ));
+
+    let (client, transport) = canned(Method::POST, Path::OrderTest, "order_test.json");
+    let params = TestNewOrderParams {
+        order: new_order(),
+        compute_commission_rates: Some(true),
+    };
+    let response = client.test_new_order(params).await.unwrap();
+    assert_request(
+        &transport,
+        Method::POST,
+        Path::OrderTest,
+        SecurityType::Trade,
+        "symbol=BTCUSDT&side=SELL&type=MARKET&quantity=10&computeCommissionRates=true",
+    );
+    let commission = response.result.standard_commission_for_order.unwrap();
+    assert_eq!(commission.taker, dec!(0.00000114));
+
+    let (client, transport) = canned(Method::GET, Path::Order, "order.json");
+    let params = QueryOrderParams {
+        symbol: String::from("LTCBTC"),
+        order_id: None,
+        orig_client_order_id: Some(String::from("myOrder1")),
+    };
+    let order = client.query_order(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::Order,
+        SecurityType::UserData,
+        "symbol=LTCBTC&origClientOrderId=myOrder1",
+    );
+    assert_eq!((order.order_id, order.status), (1, OrderStatus::New));
+
+    let (client, transport) = canned(Method::DELETE, Path::Order, "cancel_order.json");
+    let params = CancelOrderParams {
+        symbol: String::from("LTCBTC"),
+        order_id: Some(4),
+        orig_client_order_id: None,
+        new_client_order_id: None,
+        cancel_restrictions: None,
+    };
+    let order = client.cancel_order(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::DELETE,
+        Path::Order,
+        SecurityType::Trade,
+        "symbol=LTCBTC&orderId=4",
+    );
+    assert_eq!((order.order_id, order.status), (4, OrderStatus::Canceled));
+
+    let (client, transport) = canned(Method::DELETE, Path::OpenOrders, "cancel_open_orders.json");
+    let params = CancelAllOpenOrdersParams {
+        symbol: String::from("LTCBTC"),
+    };
+    let canceled = client.cancel_all_open_orders(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::DELETE,
+        Path::OpenOrders,
+        SecurityType::Trade,
+        "symbol=LTCBTC",
+    );
+    assert!(matches!(&canceled[0], CanceledOpenOrder::Order(order) if order.order_id == 11));
+    assert!(
+        matches!(&canceled[1], CanceledOpenOrder::OrderList(list) if list.order_reports.len() == 2)
+    );
+
+    let (client, transport) = canned(Method::GET, Path::OpenOrders, "open_orders.json");
+    let params = GetOpenOrdersParams { symbol: None };
+    let orders = client.current_open_orders(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::OpenOrders,
+        SecurityType::UserData,
+        "",
+    );
+    assert_eq!(orders.len(), 1);
+
+    let (client, transport) = canned(
+        Method::POST,
+        Path::OrderCancelReplace,
+        "cancel_replace.json",
+    );
+    let params = CancelReplaceOrderParams {
+        symbol: String::from("BTCUSDT"),
+        side: OrderSide::SELL,
+        order_type: OrderType::Limit,
+        cancel_replace_mode: CancelReplaceMode::StopOnFailure,
+        time_in_force: Some(TimeInForce::GTC),
+        quantity: Some(dec!(10)),
+        quote_order_qty: None,
+        price: Some(dec!(0.01)),
+        cancel_new_client_order_id: None,
+        cancel_orig_client_order_id: Some(String::from("myOrder1")),
+        cancel_order_id: None,
+        new_client_order_id: None,
+        strategy_id: None,
+        strategy_type: None,
+        stop_price: None,
+        trailing_delta: None,
+        iceberg_qty: None,
+        new_order_resp_type: None,
+        self_trade_prevention_mode: None,
+        cancel_restrictions: None,
+        order_rate_limit_exceeded_mode: None,
+    };
+    let response = client.cancel_replace_order(params).await.unwrap();
+    assert_request(
+        &transport,
+        Method::POST,
+        Path::OrderCancelReplace,
+        SecurityType::Trade,
+        "symbol=BTCUSDT&side=SELL&type=LIMIT&cancelReplaceMode=STOP_ON_FAILURE&timeInForce=GTC&quantity=10&price=0.01&cancelOrigClientOrderId=myOrder1",
+    );
+    assert!(matches!(
+        response.result,
+        CancelReplaceOrder::Success { cancel_response, .. } if cancel_response.orig_client_order_id == "myOrder1"
+    ));
+
+    let (client, transport) = canned(
+        Method::PUT,
+        Path::OrderAmendKeepPriority,
+        "amend_keep_priority.json",
+    );
+    let params = AmendOrderKeepPriorityParams {
+        symbol: String::from("BTCUSDT"),
+        order_id: Some(33),
+        orig_client_order_id: None,
+        new_client_order_id: None,
+        new_qty: dec!(5),
+    };
+    let amended = client
+        .amend_order_keep_priority(params)
+        .await
+        .unwrap()
+        .result;
+    assert_request(
+        &transport,
+        Method::PUT,
+        Path::OrderAmendKeepPriority,
+        SecurityType::Trade,
+        "symbol=BTCUSDT&orderId=33&newQty=5",
+    );
+    assert_eq!(amended.amended_order.qty, dec!(5));
+    assert!(amended.list_status.is_none());
+}
+
+#[tokio::test]
+async fn order_lists() {
+    let (client, transport) = canned(Method::POST, Path::OrderListOCO, "order_list_oco.json");
+    let params = NewOrderListOCOParams {
+        symbol: String::from("LTCBTC"),
+        list_client_order_id: None,
+        side: OrderSide::SELL,
+        quantity: dec!(1),
+        above_type: OrderType::LimitMaker,
+        above_client_order_id: None,
+        above_iceberg_qty: None,
+        above_price: Some(dec!(1.5)),
+        above_stop_price: None,
+        above_trailing_delta: None,
+        above_time_in_force: None,
+        above_strategy_id: None,
+        above_strategy_type: None,
+        below_type: OrderType::StopLoss,
+        below_client_order_id: None,
+        below_iceberg_qty: None,
+        below_price: None,
+        below_stop_price: Some(dec!(0.96)),
+        below_trailing_delta: None,
+        below_time_in_force: None,
+        below_strategy_id: None,
+        below_strategy_type: None,
+        new_order_resp_type: None,
+        self_trade_prevention_mode: None,
+    };
+    let list = client.new_order_list_oco(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::POST,
+        Path::OrderListOCO,
+        SecurityType::Trade,
+        "symbol=LTCBTC&side=SELL&quantity=1&aboveType=LIMIT_MAKER&abovePrice=1.5&belowType=STOP_LOSS&belowStopPrice=0.96",
+    );
+    assert_eq!(list.list.contingency_type, ContingencyType::OCO);
+    assert_eq!(list.order_reports.len(), 2);
+
+    let (client, transport) = canned(Method::POST, Path::OrderListOTO, "order_list_oto.json");
+    let params = NewOrderListOTOParams {
+        symbol: String::from("LTCBTC"),
+        list_client_order_id: None,
+        new_order_resp_type: None,
+        self_trade_prevention_mode: None,
+        working_type: OrderType::Limit,
+        working_side: OrderSide::SELL,
+        working_client_order_id: None,
+        working_price: dec!(1),
+        working_quantity: dec!(1),
+        working_iceberg_qty: None,
+        working_time_in_force: Some(TimeInForce::GTC),
+        working_strategy_id: None,
+        working_strategy_type: None,
+        pending_type: OrderType::LimitMaker,
+        pending_side: OrderSide::SELL,
+        pending_client_order_id: None,
+        pending_price: Some(dec!(1.5)),
+        pending_stop_price: None,
+        pending_trailing_delta: None,
+        pending_quantity: dec!(1),
+        pending_iceberg_qty: None,
+        pending_time_in_force: None,
+        pending_strategy_id: None,
+        pending_strategy_type: None,
+    };
+    let list = client.new_order_list_oto(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::POST,
+        Path::OrderListOTO,
+        SecurityType::Trade,
+        "symbol=LTCBTC&workingType=LIMIT&workingSide=SELL&workingPrice=1&workingQuantity=1&workingTimeInForce=GTC&pendingType=LIMIT_MAKER&pendingSide=SELL&pendingPrice=1.5&pendingQuantity=1",
+    );
+    assert_eq!(list.list.contingency_type, ContingencyType::OTO);
+    assert!(matches!(
+        &list.order_reports[1],
+        OrderResponse::Result(order) if order.status == OrderStatus::PendingNew
+    ));
+
+    let (client, transport) = canned(Method::POST, Path::OrderListOTOCO, "order_list_otoco.json");
+    let params = NewOrderListOTOCOParams {
+        symbol: String::from("LTCBTC"),
+        list_client_order_id: None,
+        new_order_resp_type: None,
+        self_trade_prevention_mode: None,
+        working_type: OrderType::Limit,
+        working_side: OrderSide::BUY,
+        working_client_order_id: None,
+        working_price: dec!(1),
+        working_quantity: dec!(1),
+        working_iceberg_qty: None,
+        working_time_in_force: Some(TimeInForce::GTC),
+        working_strategy_id: None,
+        working_strategy_type: None,
+        pending_side: OrderSide::SELL,
+        pending_quantity: dec!(1),
+        pending_above_type: OrderType::LimitMaker,
+        pending_above_client_order_id: None,
+        pending_above_price: Some(dec!(1.5)),
+        pending_above_stop_price: None,
+        pending_above_trailing_delta: None,
+        pending_above_iceberg_qty: None,
+        pending_above_time_in_force: None,
+        pending_above_strategy_id: None,
+        pending_above_strategy_type: None,
+        pending_below_type: Some(OrderType::StopLoss),
+        pending_below_client_order_id: None,
+        pending_below_price: None,
+        pending_below_stop_price: Some(dec!(0.96)),
+        pending_below_trailing_delta: None,
+        pending_below_iceberg_qty: None,
+        pending_below_time_in_force: None,
+        pending_below_strategy_id: None,
+        pending_below_strategy_type: None,
+    };
+    let list = client.new_order_list_otoco(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::POST,
+        Path::OrderListOTOCO,
+        SecurityType::Trade,
+        "symbol=LTCBTC&workingType=LIMIT&workingSide=BUY&workingPrice=1&workingQuantity=1&workingTimeInForce=GTC&pendingSide=SELL&pendingQuantity=1&pendingAboveType=LIMIT_MAKER&pendingAbovePrice=1.5&pendingBelowType=STOP_LOSS&pendingBelowStopPrice=0.96",
+    );
+    assert_eq!(list.list.orders.len(), 3);
+    assert_eq!(list.order_reports.len(), 3);
+
+    let (client, transport) = canned(Method::DELETE, Path::OrderList, "cancel_order_list.json");
+    let params = CancelOrderListParams {
+        symbol: String::from("LTCBTC"),
+        order_list_id: Some(1929),
+        list_client_order_id: None,
+        new_client_order_id: None,
+    };
+    let list = client.cancel_order_list(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::DELETE,
+        Path::OrderList,
+        SecurityType::Trade,
+        "symbol=LTCBTC&orderListId=1929",
+    );
+    assert_eq!(list.list.list_status_type, OrderListStatus::AllDone);
+    assert_eq!(list.order_reports[0].status, OrderStatus::Canceled);
+
+    let (client, transport) = canned(Method::GET, Path::OrderList, "order_list.json");
+    let params = QueryOrderListParams {
+        order_list_id: Some(1929),
+        orig_client_order_id: None,
+    };
+    let list = client.query_order_list(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::OrderList,
+        SecurityType::UserData,
+        "orderListId=1929",
+    );
+    assert_eq!(list.order_list_id, 1929);
+
+    let (client, transport) = canned(Method::GET, Path::OpenOrderList, "open_order_lists.json");
+    let lists = client.query_open_order_lists().await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::OpenOrderList,
+        SecurityType::UserData,
+        "",
+    );
+    assert_eq!(lists[0].orders.len(), 2);
+}
+
+fn new_sor_order() -> NewSOROrderParams {
+    NewSOROrderParams {
+        symbol: String::from("BTCUSDT"),
+        side: OrderSide::BUY,
+        order_type: OrderType::Limit,
+        time_in_force: Some(TimeInForce::GTC),
+        quantity: dec!(0.5),
+        price: Some(dec!(31000)),
+        new_client_order_id: None,
+        strategy_id: None,
+        strategy_type: None,
+        iceberg_qty: None,
+        new_order_resp_type: None,
+        self_trade_prevention_mode: None,
+    }
+}
+
+#[tokio::test]
+async fn smart_order_routing() {
+    let (client, transport) = canned(Method::POST, Path::SOROrder, "sor_order.json");
+    let response = client.new_sor_order(new_sor_order()).await.unwrap();
+    assert_request(
+        &transport,
+        Method::POST,
+        Path::SOROrder,
+        SecurityType::Trade,
+        "symbol=BTCUSDT&side=BUY&type=LIMIT&timeInForce=GTC&quantity=0.5&price=31000",
+    );
+    assert!(matches!(
+        response.result,
+        OrderResponse::Full(order) if order.result.working_floor == Some(WorkingFloor::SOR) && order.fills.len() == 1
+    ));
+
+    let (client, transport) = canned(Method::POST, Path::SOROrderTest, "order_test.json");
+    let params = TestSOROrderParams {
+        order: new_sor_order(),
+        compute_commission_rates: None,
+    };
+    let response = client.test_sor_order(params).await.unwrap();
+    assert_request(
+        &transport,
+        Method::POST,
+        Path::SOROrderTest,
+        SecurityType::Trade,
+        "symbol=BTCUSDT&side=BUY&type=LIMIT&timeInForce=GTC&quantity=0.5&price=31000",
+    );
+    assert!(response.result.discount.is_some());
+}
+
+#[tokio::test]
+async fn account() {
+    let (client, transport) = canned(Method::GET, Path::RateLimitOrder, "rate_limit_order.json");
+    let counts = client.get_unfilled_order_count().await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::RateLimitOrder,
+        SecurityType::UserData,
+        "",
+    );
+    assert_eq!(counts[1].interval, RateLimitInterval::Day);
+    assert_eq!(counts[1].limit, 160000);
+
+    let (client, transport) = canned(Method::GET, Path::Account, "account.json");
+    let params = GetAccountInformationParams {
+        omit_zero_balances: Some(true),
+    };
+    let account = client.get_account_information(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::Account,
+        SecurityType::UserData,
+        "omitZeroBalances=true",
+    );
+    assert_eq!(account.commission_rates.maker, dec!(0.0015));
+    assert_eq!(account.balances[1].asset, "LTC");
+
+    let (client, transport) = canned(Method::GET, Path::MyAllocations, "allocations.json");
+    let params = GetAllocationsParams {
+        symbol: String::from("BTCUSDT"),
+        start_time: None,
+        end_time: None,
+        from_allocation_id: None,
+        limit: None,
+        order_id: Some(1),
+    };
+    let allocations = client.get_allocations(params).await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::GET,
+        Path::MyAllocations,
+        SecurityType::UserData,
+        "symbol=BTCUSDT&orderId=1",
+    );
+    assert_eq!(allocations[0].quote_qty, dec!(5));
+}
+
+#[tokio::test]
+async fn user_data_stream() {
+    let (client, transport) = canned(Method::POST, Path::UserDataStream, "listen_key.json");
+    let listen_key = client.start_user_data_stream().await.unwrap().result;
+    assert_request(
+        &transport,
+        Method::POST,
+        Path::UserDataStream,
+        SecurityType::UserStream,
+        "",
+    );
+    assert!(listen_key.listen_key.starts_with("pqia91ma19a5s61c"));
+
+    let params = || ListenKeyParams {
+        listen_key: String::from("pqia91ma19a5s61c"),
+    };
+    let (client, transport) = canned(Method::PUT, Path::UserDataStream, "empty.json");
+    client.keepalive_user_data_stream(params()).await.unwrap();
+    assert_request(
+        &transport,
+        Method::PUT,
+        Path::UserDataStream,
+        SecurityType::UserStream,
+        "listenKey=pqia91ma19a5s61c",
+    );
+
+    let (client, transport) = canned(Method::DELETE, Path::UserDataStream, "empty.json");
+    client.close_user_data_stream(params()).await.unwrap();
+    assert_request(
+        &transport,
+        Method::DELETE,
+        Path::UserDataStream,
+        SecurityType::UserStream,
+        "listenKey=pqia91ma19a5s61c",
+    );
+}
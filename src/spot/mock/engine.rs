@@ -0,0 +1,604 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::spot::{ErrorCode, OrderSide, OrderStatus, OrderType, TimeInForce, Timestamp};
+
+use super::{MockError, MockSymbol};
+
+/// Order placement request parsed from parameters.
+#[derive(Debug)]
+pub(super) struct NewOrder {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub time_in_force: Option<TimeInForce>,
+    pub quantity: Option<Decimal>,
+    pub quote_order_qty: Option<Decimal>,
+    pub price: Option<Decimal>,
+    pub client_order_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct OrderState {
+    pub symbol: String,
+    pub order_id: i64,
+    pub client_order_id: String,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    pub orig_quote_order_qty: Decimal,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    pub order_type: OrderType,
+    pub side: OrderSide,
+    pub time: Timestamp,
+    pub update_time: Timestamp,
+}
+
+impl OrderState {
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, OrderStatus::New | OrderStatus::PartiallyFilled)
+    }
+
+    fn remaining(&self) -> Decimal {
+        self.orig_qty - self.executed_qty
+    }
+
+    fn fill(&mut self, price: Decimal, qty: Decimal, now: Timestamp) {
+        self.executed_qty += qty;
+        self.cummulative_quote_qty += price * qty;
+        self.update_time = now;
+        self.status = if self.remaining() > Decimal::ZERO {
+            OrderStatus::PartiallyFilled
+        } else {
+            OrderStatus::Filled
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Trade {
+    pub id: i64,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub time: Timestamp,
+    pub is_buyer_maker: bool,
+}
+
+/// Order book level as price and quantity.
+pub(super) type Level = (Decimal, Decimal);
+
+#[derive(Debug)]
+pub(super) struct Depth {
+    pub last_update_id: i64,
+    pub bids: Vec<Level>,
+    pub asks: Vec<Level>,
+}
+
+struct Market {
+    info: MockSymbol,
+    /// Resting order ids by price in time priority.
+    bids: BTreeMap<Decimal, VecDeque<i64>>,
+    asks: BTreeMap<Decimal, VecDeque<i64>>,
+    trades: Vec<Trade>,
+    last_update_id: i64,
+}
+
+impl Market {
+    /// Best price of the book the taker matches against.
+    fn best_opposite(&self, side: OrderSide) -> Option<Decimal> {
+        match side {
+            OrderSide::BUY => self.asks.keys().next().copied(),
+            OrderSide::SELL => self.bids.keys().next_back().copied(),
+        }
+    }
+
+    fn opposite_mut(&mut self, side: OrderSide) -> &mut BTreeMap<Decimal, VecDeque<i64>> {
+        match side {
+            OrderSide::BUY => &mut self.asks,
+            OrderSide::SELL => &mut self.bids,
+        }
+    }
+
+    fn own_mut(&mut self, side: OrderSide) -> &mut BTreeMap<Decimal, VecDeque<i64>> {
+        match side {
+            OrderSide::BUY => &mut self.bids,
+            OrderSide::SELL => &mut self.asks,
+        }
+    }
+}
+
+/// In-memory price-time priority matching engine for a single account.
+pub(super) struct Engine {
+    markets: BTreeMap<String, Market>,
+    orders: BTreeMap<i64, OrderState>,
+    next_order_id: i64,
+}
+
+impl Engine {
+    pub fn new(symbols: &[MockSymbol]) -> Self {
+        let markets = symbols
+            .iter()
+            .map(|info| {
+                let market = Market {
+                    info: info.clone(),
+                    bids: BTreeMap::new(),
+                    asks: BTreeMap::new(),
+                    trades: Vec::new(),
+                    last_update_id: 1,
+                };
+                (info.symbol.clone(), market)
+            })
+            .collect();
+
+        Self {
+            markets,
+            orders: BTreeMap::new(),
+            next_order_id: 1,
+        }
+    }
+
+    pub fn symbol(&self, symbol: &str) -> Result<&MockSymbol, MockError> {
+        self.market(symbol).map(|market| &market.info)
+    }
+
+    fn market(&self, symbol: &str) -> Result<&Market, MockError> {
+        self.markets.get(symbol).ok_or_else(bad_symbol)
+    }
+
+    /// Validate order against symbol filters without placing it.
+    pub fn validate(&self, order: &NewOrder) -> Result<(), MockError> {
+        let market = self.market(&order.symbol)?;
+        let info = &market.info;
+
+        match order.order_type {
+            OrderType::Limit | OrderType::LimitMaker => {
+                let price = order.price.ok_or_else(|| MockError::mandatory("price"))?;
+                let quantity = order
+                    .quantity
+                    .ok_or_else(|| MockError::mandatory("quantity"))?;
+                if order.order_type == OrderType::Limit && order.time_in_force.is_none() {
+                    return Err(MockError::mandatory("timeInForce"));
+                }
+                if price <= Decimal::ZERO || !is_multiple(price, info.tick_size) {
+                    return Err(filter_failure("PRICE_FILTER"));
+                }
+                check_lot_size(info, quantity)?;
+            }
+            OrderType::Market => match (order.quantity, order.quote_order_qty) {
+                (Some(quantity), None) => check_lot_size(info, quantity)?,
+                (None, Some(quote)) if quote > Decimal::ZERO => {}
+                (None, Some(_)) => return Err(filter_failure("NOTIONAL")),
+                (Some(_), Some(_)) | (None, None) => {
+                    return Err(MockError::bad_request(
+                        ErrorCode::MandatoryParamEmptyOrMalformed,
+                        "Param 'quantity' or 'quoteOrderQty' must be sent, but both were empty/null!",
+                    ));
+                }
+            },
+            _ => {
+                return Err(MockError::bad_request(
+                    ErrorCode::UnknownOrderComposition,
+                    "Unsupported order combination.",
+                ));
+            }
+        }
+
+        if order.order_type == OrderType::LimitMaker
+            && let (Some(best), Some(price)) = (market.best_opposite(order.side), order.price)
+            && crosses(order.side, price, best)
+        {
+            return Err(MockError::bad_request(
+                ErrorCode::NewOrderRejected,
+                "Order would immediately match and take.",
+            ));
+        }
+
+        let duplicate = order.client_order_id.as_ref().is_some_and(|id| {
+            self.orders
+                .values()
+                .any(|o| o.is_open() && &o.client_order_id == id)
+        });
+        if duplicate {
+            return Err(MockError::bad_request(
+                ErrorCode::NewOrderRejected,
+                "Duplicate order sent.",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Place order, match it against the book and return its state with the trades it took part in.
+    pub fn place(
+        &mut self,
+        order: NewOrder,
+        now: Timestamp,
+    ) -> Result<(OrderState, Vec<Trade>), MockError> {
+        self.validate(&order)?;
+
+        let order_id = self.next_order_id;
+        self.next_order_id += 1;
+        let mut taker = OrderState {
+            client_order_id: order
+                .client_order_id
+                .unwrap_or_else(|| format!("mock-{order_id}")),
+            symbol: order.symbol,
+            order_id,
+            price: order.price.unwrap_or_default(),
+            orig_qty: order.quantity.unwrap_or_default(),
+            executed_qty: Decimal::ZERO,
+            cummulative_quote_qty: Decimal::ZERO,
+            orig_quote_order_qty: order.quote_order_qty.unwrap_or_default(),
+            status: OrderStatus::New,
+            time_in_force: order.time_in_force.unwrap_or(TimeInForce::GTC),
+            order_type: order.order_type,
+            side: order.side,
+            time: now,
+            update_time: now,
+        };
+        let Some(market) = self.markets.get_mut(&taker.symbol) else {
+            return Err(bad_symbol());
+        };
+
+        let limit = order
+            .price
+            .filter(|_| taker.order_type != OrderType::Market);
+        let fill_or_kill = taker.time_in_force == TimeInForce::FOK
+            && available(market, &self.orders, taker.side, limit) < taker.orig_qty;
+
+        let mut trades = Vec::new();
+        if !fill_or_kill {
+            trades = match_order(market, &mut self.orders, &mut taker, limit, now);
+        }
+
+        let remaining = if taker.order_type == OrderType::Market && taker.orig_qty.is_zero() {
+            // quoteOrderQty market order: done when the book could not absorb the quote amount.
+            let unfilled = trades.is_empty() || market.best_opposite(taker.side).is_none();
+            taker.orig_qty = taker.executed_qty;
+            unfilled
+        } else {
+            taker.remaining() > Decimal::ZERO
+        };
+
+        let rests =
+            taker.order_type != OrderType::Market && taker.time_in_force == TimeInForce::GTC;
+        if remaining && rests {
+            market
+                .own_mut(taker.side)
+                .entry(taker.price)
+                .or_default()
+                .push_back(order_id);
+        } else if remaining || fill_or_kill {
+            taker.status = OrderStatus::Expired;
+        }
+        if !trades.is_empty() || (remaining && rests) {
+            market.last_update_id += 1;
+        }
+
+        self.orders.insert(order_id, taker.clone());
+        Ok((taker, trades))
+    }
+
+    pub fn order(
+        &self,
+        symbol: &str,
+        order_id: Option<i64>,
+        client_order_id: Option<&str>,
+    ) -> Result<&OrderState, MockError> {
+        self.market(symbol)?;
+        self.find(symbol, order_id, client_order_id)
+            .ok_or_else(|| MockError::bad_request(ErrorCode::NoSuchOrder, "Order does not exist."))
+    }
+
+    /// Cancel open order and return its state.
+    pub fn cancel(
+        &mut self,
+        symbol: &str,
+        order_id: Option<i64>,
+        client_order_id: Option<&str>,
+        now: Timestamp,
+    ) -> Result<OrderState, MockError> {
+        self.market(symbol)?;
+        let order_id = self
+            .find(symbol, order_id, client_order_id)
+            .filter(|order| order.is_open())
+            .map(|order| order.order_id)
+            .ok_or_else(unknown_order)?;
+
+        Ok(self.cancel_open(order_id, now))
+    }
+
+    /// Cancel all open orders on the symbol.
+    pub fn cancel_all(
+        &mut self,
+        symbol: &str,
+        now: Timestamp,
+    ) -> Result<Vec<OrderState>, MockError> {
+        self.market(symbol)?;
+        let ids: Vec<i64> = self
+            .open_orders(Some(symbol))
+            .iter()
+            .map(|order| order.order_id)
+            .collect();
+        if ids.is_empty() {
+            return Err(unknown_order());
+        }
+
+        Ok(ids
+            .into_iter()
+            .map(|id| self.cancel_open(id, now))
+            .collect())
+    }
+
+    pub fn open_orders(&self, symbol: Option<&str>) -> Vec<&OrderState> {
+        self.orders
+            .values()
+            .filter(|order| order.is_open())
+            .filter(|order| symbol.is_none_or(|symbol| order.symbol == symbol))
+            .collect()
+    }
+
+    pub fn depth(&self, symbol: &str, limit: usize) -> Result<Depth, MockError> {
+        let market = self.market(symbol)?;
+        let level = |(price, ids): (&Decimal, &VecDeque<i64>)| {
+            let qty = ids
+                .iter()
+                .filter_map(|id| self.orders.get(id))
+                .map(OrderState::remaining)
+                .sum();
+            (*price, qty)
+        };
+
+        Ok(Depth {
+            last_update_id: market.last_update_id,
+            bids: market.bids.iter().rev().take(limit).map(level).collect(),
+            asks: market.asks.iter().take(limit).map(level).collect(),
+        })
+    }
+
+    pub fn trades(&self, symbol: &str) -> Result<&[Trade], MockError> {
+        self.market(symbol).map(|market| market.trades.as_slice())
+    }
+
+    fn find(
+        &self,
+        symbol: &str,
+        order_id: Option<i64>,
+        client_order_id: Option<&str>,
+    ) -> Option<&OrderState> {
+        match (order_id, client_order_id) {
+            (Some(order_id), _) => self.orders.get(&order_id),
+            (None, Some(id)) => self
+                .orders
+                .values()
+                .rev()
+                .find(|order| order.client_order_id == id),
+            (None, None) => None,
+        }
+        .filter(|order| order.symbol == symbol)
+    }
+
+    fn cancel_open(&mut self, order_id: i64, now: Timestamp) -> OrderState {
+        let order = self
+            .orders
+            .get_mut(&order_id)
+            .expect("open order is tracked");
+        order.status = OrderStatus::Canceled;
+        order.update_time = now;
+
+        if let Some(market) = self.markets.get_mut(&order.symbol) {
+            let book = market.own_mut(order.side);
+            if let Some(queue) = book.get_mut(&order.price) {
+                queue.retain(|id| *id != order_id);
+                if queue.is_empty() {
+                    book.remove(&order.price);
+                }
+            }
+            market.last_update_id += 1;
+        }
+
+        order.clone()
+    }
+}
+
+fn match_order(
+    market: &mut Market,
+    orders: &mut BTreeMap<i64, OrderState>,
+    taker: &mut OrderState,
+    limit: Option<Decimal>,
+    now: Timestamp,
+) -> Vec<Trade> {
+    let by_quote = taker.orig_qty.is_zero();
+    let mut trades = Vec::new();
+
+    while let Some(price) = market.best_opposite(taker.side) {
+        if limit.is_some_and(|limit| !crosses(taker.side, limit, price)) {
+            break;
+        }
+        let book = market.opposite_mut(taker.side);
+        let Some(queue) = book.get_mut(&price) else {
+            break;
+        };
+        let Some(maker) = queue.front().and_then(|id| orders.get_mut(id)) else {
+            break;
+        };
+
+        let wanted = if by_quote {
+            let quote = taker.orig_quote_order_qty - taker.cummulative_quote_qty;
+            (quote / price).round_dp_with_strategy(8, RoundingStrategy::ToZero)
+        } else {
+            taker.remaining()
+        };
+        let qty = wanted.min(maker.remaining());
+        if qty.is_zero() {
+            break;
+        }
+
+        maker.fill(price, qty, now);
+        if !maker.is_open() {
+            queue.pop_front();
+            if queue.is_empty() {
+                book.remove(&price);
+            }
+        }
+        taker.fill(price, qty, now);
+        if by_quote {
+            taker.status = OrderStatus::PartiallyFilled;
+        }
+
+        let trade = Trade {
+            id: market.trades.len() as i64 + 1,
+            price,
+            qty,
+            time: now,
+            is_buyer_maker: taker.side == OrderSide::SELL,
+        };
+        market.trades.push(trade.clone());
+        trades.push(trade);
+    }
+
+    if by_quote && !trades.is_empty() {
+        taker.status = OrderStatus::Filled;
+    }
+    trades
+}
+
+/// Quantity the taker could fill at or better than `limit`.
+fn available(
+    market: &Market,
+    orders: &BTreeMap<i64, OrderState>,
+    side: OrderSide,
+    limit: Option<Decimal>,
+) -> Decimal {
+    let levels: Box<dyn Iterator<Item = (&Decimal, &VecDeque<i64>)>> = match side {
+        OrderSide::BUY => Box::new(market.asks.iter()),
+        OrderSide::SELL => Box::new(market.bids.iter().rev()),
+    };
+
+    levels
+        .take_while(|(price, _)| limit.is_none_or(|limit| crosses(side, limit, **price)))
+        .flat_map(|(_, ids)| ids)
+        .filter_map(|id| orders.get(id))
+        .map(OrderState::remaining)
+        .sum()
+}
+
+/// Whether an order on `side` with limit `price` matches a resting order at `opposite`.
+fn crosses(side: OrderSide, price: Decimal, opposite: Decimal) -> bool {
+    match side {
+        OrderSide::BUY => price >= opposite,
+        OrderSide::SELL => price <= opposite,
+    }
+}
+
+fn check_lot_size(info: &MockSymbol, quantity: Decimal) -> Result<(), MockError> {
+    if quantity <= Decimal::ZERO || !is_multiple(quantity, info.step_size) {
+        return Err(filter_failure("LOT_SIZE"));
+    }
+    Ok(())
+}
+
+/// A zero step disables the check, like Binance does.
+fn is_multiple(value: Decimal, step: Decimal) -> bool {
+    step.is_zero() || (value % step).is_zero()
+}
+
+fn filter_failure(filter: &str) -> MockError {
+    MockError::bad_request(
+        ErrorCode::InvalidMessage,
+        format!("Filter failure: {filter}"),
+    )
+}
+
+fn bad_symbol() -> MockError {
+    MockError::bad_request(ErrorCode::BadSymbol, "Invalid symbol.")
+}
+
+fn unknown_order() -> MockError {
+    MockError::bad_request(ErrorCode::CancelRejected, "Unknown order sent.")
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+
+    fn limit(side: OrderSide, price: Decimal, qty: Decimal, tif: TimeInForce) -> NewOrder {
+        NewOrder {
+            symbol: String::from("BTCUSDT"),
+            side,
+            order_type: OrderType::Limit,
+            time_in_force: Some(tif),
+            quantity: Some(qty),
+            quote_order_qty: None,
+            price: Some(price),
+            client_order_id: None,
+        }
+    }
+
+    #[test]
+    fn zero_tick_and_step_size() {
+        let symbol = MockSymbol {
+            tick_size: Decimal::ZERO,
+            step_size: Decimal::ZERO,
+            ..MockSymbol::new("BTC", "USDT")
+        };
+        let engine = Engine::new(&[symbol]);
+        let order = limit(
+            OrderSide::BUY,
+            dec!(100.0001),
+            dec!(0.123456789),
+            TimeInForce::GTC,
+        );
+        assert!(engine.validate(&order).is_ok());
+    }
+
+    #[test]
+    fn price_time_priority() {
+        let mut engine = Engine::new(&[MockSymbol::new("BTC", "USDT")]);
+        let gtc = TimeInForce::GTC;
+        engine
+            .place(limit(OrderSide::SELL, dec!(101), dec!(1), gtc), 1)
+            .unwrap();
+        let (first, _) = engine
+            .place(limit(OrderSide::SELL, dec!(100), dec!(1), gtc), 2)
+            .unwrap();
+        engine
+            .place(limit(OrderSide::SELL, dec!(100), dec!(1), gtc), 3)
+            .unwrap();
+
+        let (fok, trades) = engine
+            .place(
+                limit(OrderSide::BUY, dec!(100), dec!(3), TimeInForce::FOK),
+                4,
+            )
+            .unwrap();
+        assert_eq!(fok.status, OrderStatus::Expired);
+        assert!(trades.is_empty());
+
+        let (ioc, trades) = engine
+            .place(
+                limit(OrderSide::BUY, dec!(100), dec!(1.5), TimeInForce::IOC),
+                5,
+            )
+            .unwrap();
+        assert_eq!(ioc.status, OrderStatus::Filled);
+        assert_eq!(trades.len(), 2);
+        let first = engine.order("BTCUSDT", Some(first.order_id), None).unwrap();
+        assert_eq!(first.status, OrderStatus::Filled);
+
+        let (taker, trades) = engine
+            .place(limit(OrderSide::BUY, dec!(102), dec!(2), gtc), 6)
+            .unwrap();
+        assert_eq!(taker.status, OrderStatus::PartiallyFilled);
+        assert_eq!(trades.len(), 2);
+
+        let depth = engine.depth("BTCUSDT", 10).unwrap();
+        assert_eq!(depth.bids, vec![(dec!(102), dec!(0.5))]);
+        assert!(depth.asks.is_empty());
+    }
+}
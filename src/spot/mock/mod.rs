@@ -0,0 +1,313 @@
+//! Local mock of the Binance spot REST API for integration tests.
+//!
+//! Serves `ping`, `time`, `exchangeInfo`, `depth`, `trades`, `klines` and the order endpoints
+//! from an in-memory matching engine. Signed endpoints validate the API key, `timestamp`,
+//! `recvWindow` and `signature` like Binance does, and failures are returned as Binance error bodies.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), binance::spot::Error> {
+//! use binance::spot::{Client, mock::{MockConfig, MockServer}};
+//!
+//! let server = MockServer::start(MockConfig::default()).await?;
+//! let client = Client::new(server.client_config())?;
+//! let server_time = client.get_server_time().await?.result;
+//! # Ok(())
+//! # }
+//! ```
+
+mod engine;
+mod server;
+
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex},
+};
+
+use rust_decimal::{Decimal, dec};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+use super::{ClientConfig, Error, ErrorCode, crypto::SensitiveString};
+
+use engine::Engine;
+use server::State;
+
+pub struct MockConfig {
+    /// Expected `X-MBX-APIKEY` header value.
+    pub api_key: String,
+    /// HMAC secret key or PKCS#8 PEM encoded RSA/Ed25519 private key used to verify signatures.
+    pub api_secret: SensitiveString,
+    pub symbols: Vec<MockSymbol>,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            api_key: String::from("mock-api-key"),
+            api_secret: SensitiveString::from("mock-api-secret"),
+            symbols: vec![MockSymbol::new("BTC", "USDT")],
+        }
+    }
+}
+
+/// Symbol traded on the mock exchange.
+#[derive(Debug, Clone)]
+pub struct MockSymbol {
+    pub symbol: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    /// PRICE_FILTER tick size.
+    pub tick_size: Decimal,
+    /// LOT_SIZE step size.
+    pub step_size: Decimal,
+}
+
+impl MockSymbol {
+    /// Symbol with tick size 0.01 and step size 0.00001.
+    pub fn new(base_asset: &str, quote_asset: &str) -> Self {
+        Self {
+            symbol: format!("{base_asset}{quote_asset}"),
+            base_asset: base_asset.to_string(),
+            quote_asset: quote_asset.to_string(),
+            tick_size: dec!(0.01),
+            step_size: dec!(0.00001),
+        }
+    }
+}
+
+/// Mock server listening on a random localhost port, stopped on drop.
+pub struct MockServer {
+    addr: SocketAddr,
+    cfg: Arc<MockConfig>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    pub async fn start(cfg: MockConfig) -> Result<Self, Error> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let addr = listener.local_addr()?;
+
+        let cfg = Arc::new(cfg);
+        let state = Arc::new(State::new(
+            Arc::clone(&cfg),
+            Mutex::new(Engine::new(&cfg.symbols)),
+        )?);
+        let task = tokio::spawn(server::serve(listener, state));
+
+        Ok(Self { addr, cfg, task })
+    }
+
+    /// Base URL for `ClientConfig::base_url`, e.g. `http://127.0.0.1:40123`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Client config pointing at this server with its API key and secret.
+    pub fn client_config(&self) -> ClientConfig {
+        ClientConfig {
+            base_url: self.base_url(),
+            api_key: Some(SensitiveString::from(self.cfg.api_key.as_str())),
+            api_secret: Some(self.cfg.api_secret.clone()),
+            ..Default::default()
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Binance error body returned by the mock.
+#[derive(Debug, PartialEq)]
+struct MockError {
+    status: u16,
+    code: ErrorCode,
+    msg: String,
+}
+
+impl MockError {
+    fn new(status: u16, code: ErrorCode, msg: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            msg: msg.into(),
+        }
+    }
+
+    fn bad_request(code: ErrorCode, msg: impl Into<String>) -> Self {
+        Self::new(400, code, msg)
+    }
+
+    fn mandatory(name: &str) -> Self {
+        Self::bad_request(
+            ErrorCode::MandatoryParamEmptyOrMalformed,
+            format!("Mandatory parameter '{name}' was not sent, was empty/null, or malformed."),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spot::{
+        CancelOrderParams, Client, GetExchangeInfoParams, GetKlineListParams, GetOpenOrdersParams,
+        GetOrderBookParams, GetRecentTradesParams, KlineInterval, NewOrderParams, OrderResponse,
+        OrderSide, OrderStatus, OrderType, QueryOrderParams, RetryPolicy, TimeInForce,
+    };
+
+    fn order(side: OrderSide, order_type: OrderType, qty: Decimal) -> NewOrderParams {
+        NewOrderParams {
+            symbol: String::from("BTCUSDT"),
+            side,
+            order_type,
+            time_in_force: None,
+            quantity: Some(qty),
+            quote_order_qty: None,
+            price: None,
+            new_client_order_id: None,
+            strategy_id: None,
+            strategy_type: None,
+            stop_price: None,
+            trailing_delta: None,
+            iceberg_qty: None,
+            new_order_resp_type: None,
+            self_trade_prevention_mode: None,
+        }
+    }
+
+    fn limit(side: OrderSide, price: Decimal, qty: Decimal) -> NewOrderParams {
+        NewOrderParams {
+            time_in_force: Some(TimeInForce::GTC),
+            price: Some(price),
+            ..order(side, OrderType::Limit, qty)
+        }
+    }
+
+    #[tokio::test]
+    async fn general_endpoints() {
+        let server = MockServer::start(MockConfig::default()).await.unwrap();
+        let client = Client::new(server.client_config()).unwrap();
+
+        client.test_connectivity().await.unwrap();
+        client.get_server_time().await.unwrap();
+        let params = GetExchangeInfoParams {
+            symbol: Some(String::from("BTCUSDT")),
+            symbols: None,
+            permissions: None,
+            show_permission_sets: None,
+            symbol_status: None,
+        };
+        let info = client.get_exchange_info(params).await.unwrap().result;
+        assert_eq!(info.symbols.len(), 1);
+        assert_eq!(info.symbols[0].base_asset, "BTC");
+    }
+
+    #[tokio::test]
+    async fn order_lifecycle() {
+        let server = MockServer::start(MockConfig::default()).await.unwrap();
+        let client = Client::new(server.client_config()).unwrap();
+
+        let ask = limit(OrderSide::SELL, dec!(100.00), dec!(1.5));
+        let OrderResponse::Full(ask) = client.new_order(ask).await.unwrap().result else {
+            panic!("expected FULL response");
+        };
+        assert_eq!(ask.result.status, OrderStatus::New);
+
+        let bid = order(OrderSide::BUY, OrderType::Market, dec!(1));
+        let OrderResponse::Full(bid) = client.new_order(bid).await.unwrap().result else {
+            panic!("expected FULL response");
+        };
+        assert_eq!(bid.result.status, OrderStatus::Filled);
+        assert_eq!(bid.fills.len(), 1);
+        assert_eq!(bid.fills[0].price, dec!(100));
+        assert_eq!(bid.result.cummulative_quote_qty, dec!(100));
+
+        let params = QueryOrderParams {
+            symbol: String::from("BTCUSDT"),
+            order_id: Some(ask.result.order_id),
+            orig_client_order_id: None,
+        };
+        let queried = client.query_order(params).await.unwrap().result;
+        assert_eq!(queried.status, OrderStatus::PartiallyFilled);
+        assert_eq!(queried.executed_qty, dec!(1));
+
+        let params = GetOpenOrdersParams {
+            symbol: Some(String::from("BTCUSDT")),
+        };
+        let open = client.current_open_orders(params).await.unwrap().result;
+        assert_eq!(open.len(), 1);
+
+        let params = GetRecentTradesParams {
+            symbol: String::from("BTCUSDT"),
+            limit: None,
+        };
+        let trades = client.recent_trades_list(params).await.unwrap().result;
+        assert_eq!(trades.len(), 1);
+        assert!(!trades[0].is_buyer_maker);
+
+        let params = GetKlineListParams {
+            symbol: String::from("BTCUSDT"),
+            interval: KlineInterval::Minute1,
+            start_time: None,
+            end_time: None,
+            time_zone: None,
+            limit: None,
+        };
+        let klines = client.get_kline_list(params).await.unwrap().result;
+        assert_eq!(klines.len(), 1);
+        assert_eq!(klines[0].volume(), dec!(1));
+
+        let depth = || GetOrderBookParams {
+            symbol: String::from("BTCUSDT"),
+            limit: Some(5),
+        };
+        let book = client.get_order_book(depth()).await.unwrap().result;
+        assert!(book.bids.is_empty());
+        assert_eq!(book.asks.len(), 1);
+        assert_eq!(book.asks[0].price(), dec!(100));
+        assert_eq!(book.asks[0].qty(), dec!(0.5));
+
+        let params = CancelOrderParams {
+            symbol: String::from("BTCUSDT"),
+            order_id: Some(ask.result.order_id),
+            orig_client_order_id: None,
+            new_client_order_id: None,
+            cancel_restrictions: None,
+        };
+        let canceled = client.cancel_order(params).await.unwrap().result;
+        assert_eq!(canceled.status, OrderStatus::Canceled);
+
+        let book = client.get_order_book(depth()).await.unwrap().result;
+        assert!(book.asks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let server = MockServer::start(MockConfig::default()).await.unwrap();
+        let client = Client::new(server.client_config()).unwrap();
+
+        let unknown = NewOrderParams {
+            symbol: String::from("ETHBTC"),
+            ..limit(OrderSide::BUY, dec!(1), dec!(1))
+        };
+        let error = client.new_order(unknown).await.unwrap_err();
+        assert_eq!(error.code(), Some(ErrorCode::BadSymbol));
+
+        let off_tick = limit(OrderSide::BUY, dec!(1.001), dec!(1));
+        let error = client.new_order(off_tick).await.unwrap_err();
+        assert_eq!(error.code(), Some(ErrorCode::InvalidMessage));
+
+        let cfg = ClientConfig {
+            api_secret: Some(SensitiveString::from("wrong")),
+            retry: RetryPolicy::none(),
+            ..server.client_config()
+        };
+        let client = Client::new(cfg).unwrap();
+        let error = client
+            .new_order(limit(OrderSide::BUY, dec!(1), dec!(1)))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Some(ErrorCode::InvalidSignature));
+    }
+}
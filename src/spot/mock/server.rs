@@ -0,0 +1,683 @@
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    str::FromStr,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    Method, Request, Response,
+    body::{Bytes, Incoming},
+    header::CONTENT_TYPE,
+    server::conn::http1,
+    service::service_fn,
+};
+use hyper_util::rt::TokioIo;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+
+use crate::spot::{
+    Error, ErrorCode, HEADER_X_MBX_APIKEY, KlineInterval, OrderResponseType, OrderSide, OrderType,
+    Path, Timestamp, crypto::Signer, timestamp,
+};
+
+use super::{
+    MockConfig, MockError, MockSymbol,
+    engine::{Engine, NewOrder, OrderState, Trade},
+};
+
+/// Default `recvWindow` in milliseconds.
+const RECV_WINDOW: u64 = 5_000;
+
+const ENDPOINTS: [Path; 9] = [
+    Path::Ping,
+    Path::Time,
+    Path::ExchangeInfo,
+    Path::Depth,
+    Path::Trades,
+    Path::KLines,
+    Path::Order,
+    Path::OrderTest,
+    Path::OpenOrders,
+];
+
+pub(super) struct State {
+    cfg: Arc<MockConfig>,
+    signer: Signer,
+    engine: Mutex<Engine>,
+}
+
+/// Accept connections until the server task is aborted.
+pub(super) async fn serve(listener: TcpListener, state: Arc<State>) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let state = Arc::clone(&state);
+                async move { Ok::<_, Infallible>(state.handle(request).await) }
+            });
+            let _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+struct MockRequest<'a> {
+    method: Method,
+    query: &'a str,
+    body: &'a str,
+    api_key: Option<&'a str>,
+}
+
+impl State {
+    pub fn new(cfg: Arc<MockConfig>, engine: Mutex<Engine>) -> Result<Self, Error> {
+        let signer = Signer::new(&cfg.api_secret)?;
+        Ok(Self {
+            cfg,
+            signer,
+            engine,
+        })
+    }
+
+    fn engine(&self) -> MutexGuard<'_, Engine> {
+        self.engine.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    async fn handle(&self, request: Request<Incoming>) -> Response<Full<Bytes>> {
+        let (parts, body) = request.into_parts();
+        let body = match body.collect().await {
+            Ok(body) => String::from_utf8_lossy(&body.to_bytes()).into_owned(),
+            Err(_) => String::new(),
+        };
+        let endpoint = ENDPOINTS
+            .into_iter()
+            .find(|path| path.to_string() == parts.uri.path());
+        let request = MockRequest {
+            method: parts.method,
+            query: parts.uri.query().unwrap_or_default(),
+            body: &body,
+            api_key: parts
+                .headers
+                .get(HEADER_X_MBX_APIKEY)
+                .and_then(|value| value.to_str().ok()),
+        };
+
+        let result = match endpoint {
+            Some(endpoint) => self.route(endpoint, &request),
+            None => Ok(None),
+        };
+        match result {
+            Ok(Some(value)) => response(200, value.to_string()),
+            Ok(None) => response(404, String::new()),
+            Err(error) => {
+                let body = json!({ "code": error.code, "msg": error.msg });
+                response(error.status, body.to_string())
+            }
+        }
+    }
+
+    /// Dispatch request to its handler, `None` for unsupported method and path combinations.
+    fn route(&self, endpoint: Path, request: &MockRequest) -> Result<Option<Value>, MockError> {
+        let params = Params::from_request(request.query, request.body)?;
+        if matches!(endpoint, Path::Order | Path::OrderTest | Path::OpenOrders) {
+            self.authenticate(request, &params)?;
+        }
+
+        let value = match (&request.method, endpoint) {
+            (&Method::GET, Path::Ping) => json!({}),
+            (&Method::GET, Path::Time) => json!({ "serverTime": timestamp() }),
+            (&Method::GET, Path::ExchangeInfo) => self.exchange_info(&params)?,
+            (&Method::GET, Path::Depth) => self.depth(&params)?,
+            (&Method::GET, Path::Trades) => self.trades(&params)?,
+            (&Method::GET, Path::KLines) => self.klines(&params)?,
+            (&Method::POST, Path::Order) => self.new_order(&params)?,
+            (&Method::POST, Path::OrderTest) => {
+                self.engine().validate(&new_order(&params)?)?;
+                json!({})
+            }
+            (&Method::GET, Path::Order) => self.query_order(&params)?,
+            (&Method::DELETE, Path::Order) => self.cancel_order(&params)?,
+            (&Method::GET, Path::OpenOrders) => self.open_orders(&params)?,
+            (&Method::DELETE, Path::OpenOrders) => self.cancel_open_orders(&params)?,
+            _ => return Ok(None),
+        };
+        Ok(Some(value))
+    }
+
+    /// Check API key, `timestamp` against `recvWindow` and the signature of query string and body.
+    fn authenticate(&self, request: &MockRequest, params: &Params) -> Result<(), MockError> {
+        let api_key = request.api_key.ok_or_else(|| {
+            MockError::new(401, ErrorCode::BadApiKeyFmt, "API-key format invalid.")
+        })?;
+        if api_key != self.cfg.api_key {
+            return Err(MockError::new(
+                401,
+                ErrorCode::RejectedMbxKey,
+                "Invalid API-key, IP, or permissions for action.",
+            ));
+        }
+
+        let signature = params.required("signature")?;
+        let sent_at: Timestamp = params.required_parse("timestamp")?;
+        let recv_window = params.parse("recvWindow")?.unwrap_or(RECV_WINDOW);
+        let now = timestamp();
+        if sent_at >= now + 1_000 || now.saturating_sub(sent_at) > recv_window {
+            return Err(MockError::bad_request(
+                ErrorCode::InvalidTimestamp,
+                "Timestamp for this request is outside of the recvWindow.",
+            ));
+        }
+
+        let payload = format!("{}{}", unsigned(request.query), unsigned(request.body));
+        if self.signer.sign(&payload) != signature {
+            return Err(MockError::bad_request(
+                ErrorCode::InvalidSignature,
+                "Signature for this request is not valid.",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn exchange_info(&self, params: &Params) -> Result<Value, MockError> {
+        let engine = self.engine();
+        let symbols = match (params.get("symbol"), params.get("symbols")) {
+            (Some(symbol), _) => vec![engine.symbol(symbol)?],
+            (None, Some(symbols)) => serde_json::from_str::<Vec<String>>(symbols)
+                .map_err(|_| illegal_chars("symbols"))?
+                .iter()
+                .map(|symbol| engine.symbol(symbol))
+                .collect::<Result<_, _>>()?,
+            (None, None) => self.cfg.symbols.iter().collect(),
+        };
+
+        Ok(json!({
+            "timezone": "UTC",
+            "serverTime": timestamp(),
+            "rateLimits": [
+                rate_limit("REQUEST_WEIGHT", "MINUTE", 1, 6000),
+                rate_limit("ORDERS", "SECOND", 10, 100),
+                rate_limit("ORDERS", "DAY", 1, 200000),
+                rate_limit("RAW_REQUESTS", "MINUTE", 5, 61000),
+            ],
+            "exchangeFilters": [],
+            "symbols": symbols.into_iter().map(symbol_info).collect::<Vec<_>>(),
+        }))
+    }
+
+    fn depth(&self, params: &Params) -> Result<Value, MockError> {
+        let symbol = params.required("symbol")?;
+        let limit = params.parse("limit")?.unwrap_or(100).min(5000);
+        let depth = self.engine().depth(symbol, limit)?;
+
+        let levels = |levels: Vec<(Decimal, Decimal)>| {
+            levels
+                .into_iter()
+                .map(|(price, qty)| json!([price, qty]))
+                .collect::<Vec<_>>()
+        };
+        Ok(json!({
+            "lastUpdateId": depth.last_update_id,
+            "bids": levels(depth.bids),
+            "asks": levels(depth.asks),
+        }))
+    }
+
+    fn trades(&self, params: &Params) -> Result<Value, MockError> {
+        let symbol = params.required("symbol")?;
+        let limit = params.parse("limit")?.unwrap_or(500).min(1000);
+        let engine = self.engine();
+        let trades = engine.trades(symbol)?;
+
+        let start = trades.len().saturating_sub(limit);
+        let trades = trades[start..]
+            .iter()
+            .map(|trade| {
+                json!({
+                    "id": trade.id,
+                    "price": trade.price,
+                    "qty": trade.qty,
+                    "quoteQty": trade.price * trade.qty,
+                    "time": trade.time,
+                    "isBuyerMaker": trade.is_buyer_maker,
+                    "isBestMatch": true,
+                })
+            })
+            .collect();
+        Ok(Value::Array(trades))
+    }
+
+    /// Klines aggregated from the trades of the mock, intervals without trades are skipped.
+    fn klines(&self, params: &Params) -> Result<Value, MockError> {
+        let symbol = params.required("symbol")?;
+        let interval: KlineInterval = params
+            .enum_value("interval", ErrorCode::BadInterval, "Invalid interval.")?
+            .ok_or_else(|| MockError::mandatory("interval"))?;
+        let interval = interval_millis(&interval)
+            .ok_or_else(|| MockError::bad_request(ErrorCode::BadInterval, "Invalid interval."))?;
+        let start_time: Option<Timestamp> = params.parse("startTime")?;
+        let end_time: Option<Timestamp> = params.parse("endTime")?;
+        let limit = params.parse("limit")?.unwrap_or(500).min(1000);
+
+        let engine = self.engine();
+        let mut buckets: BTreeMap<Timestamp, Vec<&Trade>> = BTreeMap::new();
+        for trade in engine.trades(symbol)? {
+            if start_time.is_some_and(|start| trade.time < start)
+                || end_time.is_some_and(|end| trade.time > end)
+            {
+                continue;
+            }
+            let open_time = trade.time / interval * interval;
+            buckets.entry(open_time).or_default().push(trade);
+        }
+
+        let klines: Vec<Value> = buckets
+            .into_iter()
+            .map(|(open_time, trades)| kline(open_time, open_time + interval - 1, &trades))
+            .collect();
+        let skip = match start_time {
+            Some(_) => 0,
+            None => klines.len().saturating_sub(limit),
+        };
+        Ok(Value::Array(
+            klines.into_iter().skip(skip).take(limit).collect(),
+        ))
+    }
+
+    fn new_order(&self, params: &Params) -> Result<Value, MockError> {
+        let order = new_order(params)?;
+        let response_type = params
+            .enum_value(
+                "newOrderRespType",
+                ErrorCode::IllegalChars,
+                "Illegal characters found in parameter 'newOrderRespType'.",
+            )?
+            .unwrap_or(match order.order_type {
+                OrderType::Limit | OrderType::Market => OrderResponseType::FULL,
+                _ => OrderResponseType::ACK,
+            });
+
+        let mut engine = self.engine();
+        let symbol = engine.symbol(&order.symbol)?.clone();
+        let now = timestamp();
+        let (order, trades) = engine.place(order, now)?;
+
+        let mut value = json!({
+            "symbol": order.symbol,
+            "orderId": order.order_id,
+            "orderListId": -1,
+            "clientOrderId": order.client_order_id,
+            "transactTime": now,
+        });
+        if response_type == OrderResponseType::ACK {
+            return Ok(value);
+        }
+        extend(
+            &mut value,
+            json!({
+                "price": order.price,
+                "origQty": order.orig_qty,
+                "executedQty": order.executed_qty,
+                "origQuoteOrderQty": order.orig_quote_order_qty,
+                "cummulativeQuoteQty": order.cummulative_quote_qty,
+                "status": order.status,
+                "timeInForce": order.time_in_force,
+                "type": order.order_type,
+                "side": order.side,
+                "workingTime": order.time,
+                "selfTradePreventionMode": "NONE",
+            }),
+        );
+        if response_type == OrderResponseType::FULL {
+            let commission_asset = match order.side {
+                OrderSide::BUY => &symbol.base_asset,
+                OrderSide::SELL => &symbol.quote_asset,
+            };
+            let fills: Vec<Value> = trades
+                .iter()
+                .map(|trade| {
+                    json!({
+                        "price": trade.price,
+                        "qty": trade.qty,
+                        "commission": Decimal::ZERO,
+                        "commissionAsset": commission_asset,
+                        "tradeId": trade.id,
+                    })
+                })
+                .collect();
+            extend(&mut value, json!({ "fills": fills }));
+        }
+        Ok(value)
+    }
+
+    fn query_order(&self, params: &Params) -> Result<Value, MockError> {
+        let symbol = params.required("symbol")?;
+        let (order_id, client_order_id) = order_ids(params)?;
+
+        let engine = self.engine();
+        let order = engine.order(symbol, order_id, client_order_id)?;
+        Ok(order_info(order))
+    }
+
+    fn cancel_order(&self, params: &Params) -> Result<Value, MockError> {
+        let symbol = params.required("symbol")?;
+        let (order_id, client_order_id) = order_ids(params)?;
+        let cancel_id = params.get("newClientOrderId");
+
+        let now = timestamp();
+        let order = self
+            .engine()
+            .cancel(symbol, order_id, client_order_id, now)?;
+        Ok(canceled_order(&order, cancel_id, now))
+    }
+
+    fn open_orders(&self, params: &Params) -> Result<Value, MockError> {
+        let symbol = params.get("symbol");
+
+        let engine = self.engine();
+        if let Some(symbol) = symbol {
+            engine.symbol(symbol)?;
+        }
+        let orders = engine.open_orders(symbol).into_iter().map(order_info);
+        Ok(Value::Array(orders.collect()))
+    }
+
+    fn cancel_open_orders(&self, params: &Params) -> Result<Value, MockError> {
+        let symbol = params.required("symbol")?;
+
+        let now = timestamp();
+        let orders = self.engine().cancel_all(symbol, now)?;
+        let orders = orders.iter().map(|order| canceled_order(order, None, now));
+        Ok(Value::Array(orders.collect()))
+    }
+}
+
+/// Request parameters from the query string and the form encoded body.
+struct Params(BTreeMap<String, String>);
+
+impl Params {
+    fn from_request(query: &str, body: &str) -> Result<Self, MockError> {
+        let mut params = BTreeMap::new();
+        for source in [query, body] {
+            let pairs: Vec<(String, String)> =
+                serde_urlencoded::from_str(source).map_err(|_| {
+                    MockError::bad_request(
+                        ErrorCode::IllegalChars,
+                        "Illegal characters found in a parameter.",
+                    )
+                })?;
+            params.extend(pairs);
+        }
+        Ok(Self(params))
+    }
+
+    /// Parameter value, empty values are treated as not sent.
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .get(name)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+    }
+
+    fn required(&self, name: &str) -> Result<&str, MockError> {
+        self.get(name).ok_or_else(|| MockError::mandatory(name))
+    }
+
+    fn parse<T: FromStr>(&self, name: &str) -> Result<Option<T>, MockError> {
+        self.get(name)
+            .map(|value| value.parse().map_err(|_| illegal_chars(name)))
+            .transpose()
+    }
+
+    fn required_parse<T: FromStr>(&self, name: &str) -> Result<T, MockError> {
+        self.parse(name)?.ok_or_else(|| MockError::mandatory(name))
+    }
+
+    /// Parse enum by its serde name.
+    fn enum_value<T: DeserializeOwned>(
+        &self,
+        name: &str,
+        code: ErrorCode,
+        msg: &str,
+    ) -> Result<Option<T>, MockError> {
+        self.get(name)
+            .map(|value| {
+                serde_json::from_value(Value::String(value.to_string()))
+                    .map_err(|_| MockError::bad_request(code, msg))
+            })
+            .transpose()
+    }
+}
+
+fn new_order(params: &Params) -> Result<NewOrder, MockError> {
+    Ok(NewOrder {
+        symbol: params.required("symbol")?.to_string(),
+        side: params
+            .enum_value("side", ErrorCode::InvalidSide, "Invalid side.")?
+            .ok_or_else(|| MockError::mandatory("side"))?,
+        order_type: params
+            .enum_value("type", ErrorCode::InvalidOrderType, "Invalid orderType.")?
+            .ok_or_else(|| MockError::mandatory("type"))?,
+        time_in_force: params.enum_value(
+            "timeInForce",
+            ErrorCode::InvalidTif,
+            "Invalid timeInForce.",
+        )?,
+        quantity: params.parse("quantity")?,
+        quote_order_qty: params.parse("quoteOrderQty")?,
+        price: params.parse("price")?,
+        client_order_id: params.get("newClientOrderId").map(String::from),
+    })
+}
+
+/// `orderId` and `origClientOrderId`, at least one of them is required.
+fn order_ids(params: &Params) -> Result<(Option<i64>, Option<&str>), MockError> {
+    let order_id = params.parse("orderId")?;
+    let client_order_id = params.get("origClientOrderId");
+    if order_id.is_none() && client_order_id.is_none() {
+        return Err(MockError::bad_request(
+            ErrorCode::MandatoryParamEmptyOrMalformed,
+            "Param 'origClientOrderId' or 'orderId' must be sent, but both were empty/null!",
+        ));
+    }
+    Ok((order_id, client_order_id))
+}
+
+/// Query string or body without the `signature` parameter.
+fn unsigned(params: &str) -> String {
+    params
+        .split('&')
+        .filter(|param| !param.starts_with("signature="))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn interval_millis(interval: &KlineInterval) -> Option<u64> {
+    const MINUTE: u64 = 60_000;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    let millis = match interval {
+        KlineInterval::Second1 => 1_000,
+        KlineInterval::Minute1 => MINUTE,
+        KlineInterval::Minute3 => 3 * MINUTE,
+        KlineInterval::Minute5 => 5 * MINUTE,
+        KlineInterval::Minute15 => 15 * MINUTE,
+        KlineInterval::Minute30 => 30 * MINUTE,
+        KlineInterval::Hour1 => HOUR,
+        KlineInterval::Hour2 => 2 * HOUR,
+        KlineInterval::Hour4 => 4 * HOUR,
+        KlineInterval::Hour6 => 6 * HOUR,
+        KlineInterval::Hour8 => 8 * HOUR,
+        KlineInterval::Hour12 => 12 * HOUR,
+        KlineInterval::Day1 => DAY,
+        KlineInterval::Day3 => 3 * DAY,
+        // Calendar aligned intervals are not supported by the mock.
+        KlineInterval::Week1 | KlineInterval::Month1 => return None,
+    };
+    Some(millis)
+}
+
+fn kline(open_time: Timestamp, close_time: Timestamp, trades: &[&Trade]) -> Value {
+    let price = |trade: Option<&&Trade>| trade.map(|trade| trade.price).unwrap_or_default();
+    let high = trades
+        .iter()
+        .map(|trade| trade.price)
+        .max()
+        .unwrap_or_default();
+    let low = trades
+        .iter()
+        .map(|trade| trade.price)
+        .min()
+        .unwrap_or_default();
+    let volume: Decimal = trades.iter().map(|trade| trade.qty).sum();
+    let quote_volume: Decimal = trades.iter().map(|trade| trade.price * trade.qty).sum();
+    let taker_buys = trades.iter().filter(|trade| !trade.is_buyer_maker);
+    let taker_buy_volume: Decimal = taker_buys.clone().map(|trade| trade.qty).sum();
+    let taker_buy_quote_volume: Decimal = taker_buys.map(|trade| trade.price * trade.qty).sum();
+
+    json!([
+        open_time,
+        price(trades.first()),
+        high,
+        low,
+        price(trades.last()),
+        volume,
+        close_time,
+        quote_volume,
+        trades.len(),
+        taker_buy_volume,
+        taker_buy_quote_volume,
+        "0",
+    ])
+}
+
+fn order_info(order: &OrderState) -> Value {
+    json!({
+        "symbol": order.symbol,
+        "orderId": order.order_id,
+        "orderListId": -1,
+        "clientOrderId": order.client_order_id,
+        "price": order.price,
+        "origQty": order.orig_qty,
+        "executedQty": order.executed_qty,
+        "cummulativeQuoteQty": order.cummulative_quote_qty,
+        "status": order.status,
+        "timeInForce": order.time_in_force,
+        "type": order.order_type,
+        "side": order.side,
+        "stopPrice": Decimal::ZERO,
+        "icebergQty": Decimal::ZERO,
+        "time": order.time,
+        "updateTime": order.update_time,
+        "isWorking": true,
+        "workingTime": order.time,
+        "origQuoteOrderQty": order.orig_quote_order_qty,
+        "selfTradePreventionMode": "NONE",
+    })
+}
+
+fn canceled_order(order: &OrderState, cancel_id: Option<&str>, now: Timestamp) -> Value {
+    let client_order_id = cancel_id
+        .map(String::from)
+        .unwrap_or_else(|| format!("mock-cancel-{}", order.order_id));
+    json!({
+        "symbol": order.symbol,
+        "origClientOrderId": order.client_order_id,
+        "orderId": order.order_id,
+        "orderListId": -1,
+        "clientOrderId": client_order_id,
+        "transactTime": now,
+        "price": order.price,
+        "origQty": order.orig_qty,
+        "executedQty": order.executed_qty,
+        "origQuoteOrderQty": order.orig_quote_order_qty,
+        "cummulativeQuoteQty": order.cummulative_quote_qty,
+        "status": order.status,
+        "timeInForce": order.time_in_force,
+        "type": order.order_type,
+        "side": order.side,
+        "selfTradePreventionMode": "NONE",
+    })
+}
+
+fn symbol_info(symbol: &MockSymbol) -> Value {
+    json!({
+        "symbol": symbol.symbol,
+        "status": "TRADING",
+        "baseAsset": symbol.base_asset,
+        "baseAssetPrecision": 8,
+        "quoteAsset": symbol.quote_asset,
+        "quotePrecision": 8,
+        "quoteAssetPrecision": 8,
+        "baseCommissionPrecision": 8,
+        "quoteCommissionPrecision": 8,
+        "orderTypes": ["LIMIT", "LIMIT_MAKER", "MARKET"],
+        "icebergAllowed": false,
+        "ocoAllowed": false,
+        "otoAllowed": false,
+        "quoteOrderQtyMarketAllowed": true,
+        "allowTrailingStop": false,
+        "cancelReplaceAllowed": false,
+        "amendAllowed": false,
+        "isSpotTradingAllowed": true,
+        "isMarginTradingAllowed": false,
+        "filters": [
+            {
+                "filterType": "PRICE_FILTER",
+                "minPrice": symbol.tick_size,
+                "maxPrice": "1000000.00000000",
+                "tickSize": symbol.tick_size,
+            },
+            {
+                "filterType": "LOT_SIZE",
+                "minQty": symbol.step_size,
+                "maxQty": "9000.00000000",
+                "stepSize": symbol.step_size,
+            },
+        ],
+        "permissions": [],
+        "permissionSets": [["SPOT"]],
+        "defaultSelfTradePreventionMode": "NONE",
+        "allowedSelfTradePreventionModes": ["NONE"],
+    })
+}
+
+fn rate_limit(rate_limit_type: &str, interval: &str, interval_num: u64, limit: u64) -> Value {
+    json!({
+        "rateLimitType": rate_limit_type,
+        "interval": interval,
+        "intervalNum": interval_num,
+        "limit": limit,
+    })
+}
+
+/// Merge fields of `other` object into `value` object.
+fn extend(value: &mut Value, other: Value) {
+    if let (Value::Object(value), Value::Object(other)) = (value, other) {
+        value.extend(other);
+    }
+}
+
+fn illegal_chars(name: &str) -> MockError {
+    MockError::bad_request(
+        ErrorCode::IllegalChars,
+        format!("Illegal characters found in parameter '{name}'."),
+    )
+}
+
+fn response(status: u16, body: String) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = hyper::StatusCode::from_u16(status).unwrap_or_default();
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        hyper::header::HeaderValue::from_static("application/json;charset=UTF-8"),
+    );
+    response
+}
@@ -3,11 +3,31 @@ mod client;
 mod crypto;
 mod enums;
 mod error;
+#[cfg(test)]
+mod fixtures;
+#[cfg(feature = "mock")]
+pub mod mock;
+mod order_book;
+mod rate_limit;
+mod retry;
 mod serde;
+mod spot_api;
+pub mod stream;
+mod symbol_registry;
+mod transport;
 mod url;
+mod validation;
+pub mod ws_api;
 
 pub use api::*;
 pub use client::*;
 pub use enums::*;
 pub use error::*;
+pub use order_book::*;
+pub use rate_limit::*;
+pub use retry::*;
+pub use spot_api::*;
+pub use symbol_registry::*;
+pub use transport::*;
 pub use url::*;
+pub use validation::*;
@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+
+use crate::spot::{
+    Error, GetOrderBookParams, OrderBook, OrderLevel, SpotApi, stream::DepthUpdateEvent,
+};
+
+/// Side of an order book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookSide {
+    Bid,
+    Ask,
+}
+
+/// Whether a [`LocalOrderBook`] can be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookStatus {
+    Synced,
+    /// No snapshot or a sequence gap was detected, updates are buffered until a new snapshot is set.
+    NeedsSnapshot,
+}
+
+/// Order book kept up to date from a depth snapshot and `<symbol>@depth` diff events,
+/// following the procedure documented by Binance:
+///
+/// 1. Buffer depth updates while fetching a snapshot.
+/// 2. Refetch the snapshot while it is older than the first buffered update.
+/// 3. Drop buffered updates already included in the snapshot.
+/// 4. Apply each update whose first update id follows the last applied one, or start over.
+///
+/// ```no_run
+/// # async fn run(client: binance::spot::Client, mut streams: binance::spot::stream::MarketStreams) -> Result<(), binance::spot::Error> {
+/// use binance::spot::{BookStatus, LocalOrderBook, stream::{Event, StreamMessage}};
+///
+/// let mut book = LocalOrderBook::new("BNBBTC");
+/// while let Some(message) = streams.next().await {
+///     match message? {
+///         StreamMessage::Event(event) => {
+///             if let Event::DepthUpdate(update) = event.event {
+///                 if book.update(update) == BookStatus::NeedsSnapshot {
+///                     book.sync(&client, Some(5000)).await?;
+///                 }
+///             }
+///         }
+///         StreamMessage::Gap { .. } => book.reset(),
+///         _ => {}
+///     }
+///     println!("{:?} {:?}", book.best_bid(), book.best_ask());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct LocalOrderBook {
+    symbol: String,
+    last_update_id: Option<i64>,
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+    buffer: Vec<DepthUpdateEvent>,
+}
+
+impl LocalOrderBook {
+    /// Snapshot fetches before giving up because every snapshot is older than the buffered updates.
+    const SYNC_ATTEMPTS: u32 = 3;
+
+    pub fn new(symbol: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            last_update_id: None,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn status(&self) -> BookStatus {
+        match self.last_update_id {
+            Some(_) => BookStatus::Synced,
+            None => BookStatus::NeedsSnapshot,
+        }
+    }
+
+    /// Update id of the last applied snapshot or update.
+    pub fn last_update_id(&self) -> Option<i64> {
+        self.last_update_id
+    }
+
+    /// Clear the book, e.g. after updates were missed while reconnecting.
+    pub fn reset(&mut self) {
+        self.last_update_id = None;
+        self.bids.clear();
+        self.asks.clear();
+        self.buffer.clear();
+    }
+
+    /// Apply a depth update, or buffer it while waiting for a snapshot.
+    /// Updates of other symbols are ignored.
+    pub fn update(&mut self, event: DepthUpdateEvent) -> BookStatus {
+        if event.symbol != self.symbol {
+            return self.status();
+        }
+        let Some(last_update_id) = self.last_update_id else {
+            self.buffer.push(event);
+            return BookStatus::NeedsSnapshot;
+        };
+
+        if event.final_update_id <= last_update_id {
+            return BookStatus::Synced;
+        }
+        if event.first_update_id > last_update_id + 1 {
+            self.reset();
+            self.buffer.push(event);
+            return BookStatus::NeedsSnapshot;
+        }
+        self.apply(event);
+        BookStatus::Synced
+    }
+
+    /// Set a snapshot and apply the buffered updates following it. Returns
+    /// [`BookStatus::NeedsSnapshot`] if the snapshot is older than the buffered updates.
+    pub fn set_snapshot(&mut self, snapshot: OrderBook) -> BookStatus {
+        if let Some(first) = self.buffer.first()
+            && snapshot.last_update_id < first.first_update_id
+        {
+            return BookStatus::NeedsSnapshot;
+        }
+
+        self.bids = levels(snapshot.bids);
+        self.asks = levels(snapshot.asks);
+        self.last_update_id = Some(snapshot.last_update_id);
+        let mut buffer = std::mem::take(&mut self.buffer).into_iter();
+        for event in buffer.by_ref() {
+            if self.update(event) == BookStatus::NeedsSnapshot {
+                // The gap reset the book, the updates following it are kept for the next snapshot.
+                self.buffer.extend(buffer);
+                return BookStatus::NeedsSnapshot;
+            }
+        }
+        BookStatus::Synced
+    }
+
+    /// Fetch snapshots until one can be applied to the buffered updates.
+    pub async fn sync(&mut self, api: &impl SpotApi, limit: Option<u64>) -> Result<(), Error> {
+        for _ in 0..Self::SYNC_ATTEMPTS {
+            let params = GetOrderBookParams {
+                symbol: self.symbol.clone(),
+                limit,
+            };
+            let snapshot = api.get_order_book(params).await?;
+            if self.set_snapshot(snapshot) == BookStatus::Synced {
+                return Ok(());
+            }
+        }
+        Err(Error::Msg(format!(
+            "order book snapshot of {} is older than the buffered updates",
+            self.symbol
+        )))
+    }
+
+    /// Best bid price and quantity.
+    pub fn best_bid(&self) -> Option<(Decimal, Decimal)> {
+        self.bids
+            .last_key_value()
+            .map(|(price, qty)| (*price, *qty))
+    }
+
+    /// Best ask price and quantity.
+    pub fn best_ask(&self) -> Option<(Decimal, Decimal)> {
+        self.asks
+            .first_key_value()
+            .map(|(price, qty)| (*price, *qty))
+    }
+
+    /// Levels from the best price outwards.
+    pub fn levels(&self, side: BookSide) -> Box<dyn Iterator<Item = (Decimal, Decimal)> + '_> {
+        match side {
+            BookSide::Bid => Box::new(self.bids.iter().rev().map(|(p, q)| (*p, *q))),
+            BookSide::Ask => Box::new(self.asks.iter().map(|(p, q)| (*p, *q))),
+        }
+    }
+
+    /// Quantity at exactly `price`, zero without a level.
+    pub fn depth_at(&self, side: BookSide, price: Decimal) -> Decimal {
+        let levels = match side {
+            BookSide::Bid => &self.bids,
+            BookSide::Ask => &self.asks,
+        };
+        levels.get(&price).copied().unwrap_or_default()
+    }
+
+    /// Quantity from the best price up to and including `price`.
+    pub fn cumulative_volume(&self, side: BookSide, price: Decimal) -> Decimal {
+        match side {
+            BookSide::Bid => self.bids.range(price..).map(|(_, qty)| qty).sum(),
+            BookSide::Ask => self.asks.range(..=price).map(|(_, qty)| qty).sum(),
+        }
+    }
+
+    /// Volume weighted average price to fill `qty` against `side`, e.g. [`BookSide::Ask`]
+    /// for a market buy. `None` if the side has less than `qty`.
+    pub fn vwap(&self, side: BookSide, qty: Decimal) -> Option<Decimal> {
+        if qty <= Decimal::ZERO {
+            return None;
+        }
+        let mut remaining = qty;
+        let mut notional = Decimal::ZERO;
+        for (price, level_qty) in self.levels(side) {
+            let fill = remaining.min(level_qty);
+            notional += fill * price;
+            remaining -= fill;
+            if remaining.is_zero() {
+                return Some(notional / qty);
+            }
+        }
+        None
+    }
+
+    fn apply(&mut self, event: DepthUpdateEvent) {
+        for (levels, updates) in [(&mut self.bids, event.bids), (&mut self.asks, event.asks)] {
+            for level in updates {
+                if level.qty().is_zero() {
+                    levels.remove(&level.price());
+                } else {
+                    levels.insert(level.price(), level.qty());
+                }
+            }
+        }
+        self.last_update_id = Some(event.final_update_id);
+    }
+}
+
+fn levels(levels: Vec<OrderLevel>) -> BTreeMap<Decimal, Decimal> {
+    levels
+        .into_iter()
+        .filter(|level| !level.qty().is_zero())
+        .map(|level| (level.price(), level.qty()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use reqwest::Method;
+    use rust_decimal::dec;
+
+    use super::*;
+    use crate::spot::{CannedTransport, Client, ClientConfig, HttpResponse};
+
+    fn level(price: Decimal, qty: Decimal) -> OrderLevel {
+        serde_json::from_value(serde_json::json!([price, qty])).unwrap()
+    }
+
+    fn update(
+        first: i64,
+        last: i64,
+        bids: &[(Decimal, Decimal)],
+        asks: &[(Decimal, Decimal)],
+    ) -> DepthUpdateEvent {
+        let levels = |levels: &[(Decimal, Decimal)]| {
+            levels
+                .iter()
+                .map(|(price, qty)| level(*price, *qty))
+                .collect()
+        };
+        DepthUpdateEvent {
+            event_time: 0,
+            symbol: String::from("BNBBTC"),
+            first_update_id: first,
+            final_update_id: last,
+            bids: levels(bids),
+            asks: levels(asks),
+        }
+    }
+
+    fn snapshot(last_update_id: i64) -> OrderBook {
+        OrderBook {
+            last_update_id,
+            bids: vec![level(dec!(9), dec!(1)), level(dec!(8), dec!(2))],
+            asks: vec![level(dec!(10), dec!(1)), level(dec!(11), dec!(3))],
+        }
+    }
+
+    #[test]
+    fn sync_from_snapshot() {
+        let mut book = LocalOrderBook::new("BNBBTC");
+
+        assert_eq!(
+            book.update(update(95, 100, &[(dec!(7), dec!(1))], &[])),
+            BookStatus::NeedsSnapshot
+        );
+        assert_eq!(
+            book.update(update(101, 104, &[(dec!(9), dec!(0))], &[])),
+            BookStatus::NeedsSnapshot
+        );
+        assert_eq!(book.set_snapshot(snapshot(90)), BookStatus::NeedsSnapshot);
+        assert_eq!(book.set_snapshot(snapshot(102)), BookStatus::Synced);
+
+        // The first update is part of the snapshot, the second removes the best bid.
+        assert_eq!(book.last_update_id(), Some(104));
+        assert_eq!(book.best_bid(), Some((dec!(8), dec!(2))));
+        assert_eq!(book.depth_at(BookSide::Bid, dec!(7)), Decimal::ZERO);
+
+        assert_eq!(
+            book.update(update(100, 104, &[(dec!(1), dec!(1))], &[])),
+            BookStatus::Synced
+        );
+        assert_eq!(
+            book.update(update(105, 106, &[], &[(dec!(10.5), dec!(2))])),
+            BookStatus::Synced
+        );
+        assert_eq!(book.best_ask(), Some((dec!(10), dec!(1))));
+        assert_eq!(book.depth_at(BookSide::Ask, dec!(10.5)), dec!(2));
+        assert_eq!(book.depth_at(BookSide::Bid, dec!(1)), Decimal::ZERO);
+
+        assert_eq!(
+            book.update(update(108, 110, &[], &[])),
+            BookStatus::NeedsSnapshot
+        );
+        assert_eq!(book.best_bid(), None);
+        assert_eq!(book.set_snapshot(snapshot(109)), BookStatus::Synced);
+        assert_eq!(book.last_update_id(), Some(110));
+    }
+
+    #[test]
+    fn gap_in_buffered_updates() {
+        let mut book = LocalOrderBook::new("BNBBTC");
+        book.update(update(101, 104, &[], &[]));
+        book.update(update(106, 108, &[(dec!(7), dec!(1))], &[]));
+        book.update(update(109, 110, &[(dec!(6), dec!(1))], &[]));
+
+        // 105 was missed, the updates from 106 on wait for the next snapshot.
+        assert_eq!(book.set_snapshot(snapshot(102)), BookStatus::NeedsSnapshot);
+        assert_eq!(book.last_update_id(), None);
+        assert_eq!(book.set_snapshot(snapshot(107)), BookStatus::Synced);
+        assert_eq!(book.last_update_id(), Some(110));
+        assert_eq!(book.depth_at(BookSide::Bid, dec!(7)), dec!(1));
+        assert_eq!(book.depth_at(BookSide::Bid, dec!(6)), dec!(1));
+    }
+
+    #[tokio::test]
+    async fn sync_with_client() {
+        let transport = Arc::new(CannedTransport::new());
+        let depth = r#"{"lastUpdateId":102,"bids":[["9","1"],["8","2"]],"asks":[["10","1"]]}"#;
+        transport.push(Method::GET, "/api/v3/depth", HttpResponse::json(200, depth));
+        let cfg = ClientConfig {
+            transport: Some(transport.clone()),
+            ..Default::default()
+        };
+        let client = Client::new(cfg).unwrap();
+
+        let mut book = LocalOrderBook::new("BNBBTC");
+        book.update(update(101, 104, &[(dec!(9), dec!(0))], &[]));
+        book.sync(&client, Some(5)).await.unwrap();
+        assert_eq!(book.last_update_id(), Some(104));
+        assert_eq!(book.best_bid(), Some((dec!(8), dec!(2))));
+        assert!(
+            transport.requests()[0]
+                .url
+                .ends_with("/api/v3/depth?symbol=BNBBTC&limit=5")
+        );
+    }
+
+    #[test]
+    fn book_queries() {
+        let mut book = LocalOrderBook::new("BNBBTC");
+        book.set_snapshot(snapshot(1));
+
+        assert_eq!(book.cumulative_volume(BookSide::Bid, dec!(8)), dec!(3));
+        assert_eq!(book.cumulative_volume(BookSide::Bid, dec!(8.5)), dec!(1));
+        assert_eq!(book.cumulative_volume(BookSide::Ask, dec!(11)), dec!(4));
+        assert_eq!(book.vwap(BookSide::Ask, dec!(1)), Some(dec!(10)));
+        assert_eq!(book.vwap(BookSide::Ask, dec!(2)), Some(dec!(10.5)));
+        assert_eq!(book.vwap(BookSide::Bid, dec!(3)), Some(dec!(25) / dec!(3)));
+        assert_eq!(book.vwap(BookSide::Bid, dec!(4)), None);
+    }
+}
@@ -0,0 +1,649 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use reqwest::Method;
+
+use super::{
+    Headers, Path, RateLimit, RateLimitInterval, RateLimiter, Timestamp, UnfilledOrderCount,
+    client::timestamp,
+};
+
+/// Rate limit window as written in response headers: (intervalNum)(intervalLetter), e.g. 1M or 10S.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RateLimitWindow {
+    pub interval: RateLimitInterval,
+    pub interval_num: u64,
+}
+
+impl FromStr for RateLimitWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s.len().saturating_sub(1);
+        let (num, letter) = s.split_at_checked(split).ok_or("empty rate limit window")?;
+        let interval = match letter.to_ascii_uppercase().as_str() {
+            "S" => RateLimitInterval::Second,
+            "M" => RateLimitInterval::Minute,
+            "H" => RateLimitInterval::Hour,
+            "D" => RateLimitInterval::Day,
+            _ => return Err(format!("unknown rate limit interval: {s}")),
+        };
+        let interval_num = num
+            .parse()
+            .map_err(|_| format!("invalid rate limit interval number: {s}"))?;
+
+        Ok(Self {
+            interval,
+            interval_num,
+        })
+    }
+}
+
+/// Latest rate limit usage reported by Binance in response headers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimitUsage {
+    /// Request weight used by the IP per window.
+    pub used_weight: BTreeMap<RateLimitWindow, u64>,
+    /// Orders placed by the account per window.
+    pub order_count: BTreeMap<RateLimitWindow, u64>,
+    /// Time of the last response that carried usage headers.
+    pub updated_at: Option<Timestamp>,
+}
+
+impl RateLimitUsage {
+    /// Merge counters from response headers, windows missing in the headers keep their last values.
+    pub fn update(&mut self, headers: &Headers) {
+        self.update_at(headers, timestamp());
+    }
+
+    fn update_at(&mut self, headers: &Headers, now: Timestamp) {
+        if headers.used_weight.is_empty() && headers.order_count.is_empty() {
+            return;
+        }
+        self.used_weight.extend(&headers.used_weight);
+        self.order_count.extend(&headers.order_count);
+        self.updated_at = Some(now);
+    }
+}
+
+/// Client-side view of the ORDERS rate limits.
+///
+/// Windows come from `ExchangeInfo::rate_limits` and are synchronized with
+/// `Client::get_unfilled_order_count`. Counters reset on interval boundaries,
+/// the same way Binance resets them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderRateBudget {
+    windows: Vec<OrderWindow>,
+}
+
+/// One ORDERS rate limit window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderWindow {
+    pub interval: RateLimitInterval,
+    pub interval_num: u64,
+    pub limit: u64,
+    /// Orders counted in the window that started at `started_at`.
+    pub count: u64,
+    pub started_at: Timestamp,
+}
+
+impl OrderWindow {
+    fn duration(&self) -> u64 {
+        self.interval.millis() * self.interval_num
+    }
+
+    fn start_of(&self, now: Timestamp) -> Timestamp {
+        let duration = self.duration();
+        now / duration * duration
+    }
+
+    /// Orders counted in the window that contains `now`.
+    fn count_at(&self, now: Timestamp) -> u64 {
+        if self.start_of(now) == self.started_at {
+            self.count
+        } else {
+            0
+        }
+    }
+
+    fn add_at(&mut self, orders: u64, now: Timestamp) {
+        let started_at = self.start_of(now);
+        if started_at != self.started_at {
+            self.started_at = started_at;
+            self.count = 0;
+        }
+        self.count += orders;
+    }
+}
+
+impl OrderRateBudget {
+    /// Create budget from ORDERS limits, other rate limit types and zero length windows are ignored.
+    pub fn new(rate_limits: &[RateLimit]) -> Self {
+        let windows = rate_limits
+            .iter()
+            .filter(|r| r.rate_limit_type == RateLimiter::Orders && r.interval_num > 0)
+            .map(|r| OrderWindow {
+                interval: r.interval,
+                interval_num: r.interval_num,
+                limit: r.limit,
+                count: 0,
+                started_at: 0,
+            })
+            .collect();
+
+        Self { windows }
+    }
+
+    pub fn windows(&self) -> &[OrderWindow] {
+        &self.windows
+    }
+
+    /// Replace local counters with the unfilled order count reported by Binance.
+    pub fn sync(&mut self, counts: &[UnfilledOrderCount]) {
+        self.sync_at(counts, timestamp());
+    }
+
+    /// Replace local counters with X-MBX-ORDER-COUNT-* values of an order placement response.
+    pub fn sync_headers(&mut self, headers: &Headers) {
+        self.sync_headers_at(headers, timestamp());
+    }
+
+    /// Count orders that were just sent.
+    pub fn record(&mut self, orders: u64) {
+        self.record_at(orders, timestamp());
+    }
+
+    /// Whether `orders` more orders fit into every window right now.
+    pub fn can_place(&self, orders: u64) -> bool {
+        self.can_place_at(orders, timestamp())
+    }
+
+    /// The number of orders that still fit into every window right now.
+    /// None if no ORDERS limits are known.
+    pub fn remaining(&self) -> Option<u64> {
+        self.remaining_at(timestamp())
+    }
+
+    fn sync_headers_at(&mut self, headers: &Headers, now: Timestamp) {
+        for window in self.windows.iter_mut() {
+            let key = RateLimitWindow {
+                interval: window.interval,
+                interval_num: window.interval_num,
+            };
+            if let Some(count) = headers.order_count.get(&key) {
+                window.count = *count;
+                window.started_at = window.start_of(now);
+            }
+        }
+    }
+
+    fn sync_at(&mut self, counts: &[UnfilledOrderCount], now: Timestamp) {
+        for c in counts
+            .iter()
+            .filter(|c| c.rate_limit_type == RateLimiter::Orders && c.interval_num > 0)
+        {
+            let position = self
+                .windows
+                .iter()
+                .position(|w| w.interval == c.interval && w.interval_num == c.interval_num);
+            let window = match position {
+                Some(i) => &mut self.windows[i],
+                None => {
+                    self.windows.push(OrderWindow {
+                        interval: c.interval,
+                        interval_num: c.interval_num,
+                        limit: c.limit,
+                        count: 0,
+                        started_at: 0,
+                    });
+                    self.windows.last_mut().expect("window was just pushed")
+                }
+            };
+            window.limit = c.limit;
+            window.count = c.count;
+            window.started_at = window.start_of(now);
+        }
+    }
+
+    fn record_at(&mut self, orders: u64, now: Timestamp) {
+        for window in self.windows.iter_mut() {
+            window.add_at(orders, now);
+        }
+    }
+
+    fn can_place_at(&self, orders: u64, now: Timestamp) -> bool {
+        self.remaining_at(now).is_none_or(|r| orders <= r)
+    }
+
+    fn remaining_at(&self, now: Timestamp) -> Option<u64> {
+        self.windows
+            .iter()
+            .map(|w| w.limit.saturating_sub(w.count_at(now)))
+            .min()
+    }
+}
+
+/// What the client does with a request that would exceed REQUEST_WEIGHT or RAW_REQUESTS limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleMode {
+    /// Wait until the request fits into every window.
+    Delay,
+    /// Fail with `Error::Throttled` without sending the request.
+    Reject,
+}
+
+/// Client-side REQUEST_WEIGHT and RAW_REQUESTS limiter.
+///
+/// Windows come from `ExchangeInfo::rate_limits`. REQUEST_WEIGHT counters are raised to
+/// X-MBX-USED-WEIGHT-* values after every response, so weight spent by other clients
+/// sharing the same IP is taken into account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestLimiter {
+    mode: ThrottleMode,
+    windows: Vec<RequestWindow>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RequestWindow {
+    rate_limit_type: RateLimiter,
+    interval: RateLimitInterval,
+    interval_num: u64,
+    limit: u64,
+    count: u64,
+    started_at: Timestamp,
+}
+
+impl RequestWindow {
+    fn duration(&self) -> u64 {
+        self.interval.millis() * self.interval_num
+    }
+
+    fn start_of(&self, now: Timestamp) -> Timestamp {
+        let duration = self.duration();
+        now / duration * duration
+    }
+
+    fn roll(&mut self, now: Timestamp) {
+        let started_at = self.start_of(now);
+        if started_at != self.started_at {
+            self.started_at = started_at;
+            self.count = 0;
+        }
+    }
+
+    fn cost(&self, weight: u64) -> u64 {
+        match self.rate_limit_type {
+            RateLimiter::RawRequests => 1,
+            _ => weight,
+        }
+    }
+}
+
+impl RequestLimiter {
+    /// Create limiter from REQUEST_WEIGHT and RAW_REQUESTS limits, other rate limit types
+    /// and zero length windows are ignored.
+    pub fn new(rate_limits: &[RateLimit], mode: ThrottleMode) -> Self {
+        let windows = rate_limits
+            .iter()
+            .filter(|r| {
+                matches!(
+                    r.rate_limit_type,
+                    RateLimiter::RequestWeight | RateLimiter::RawRequests
+                ) && r.interval_num > 0
+            })
+            .map(|r| RequestWindow {
+                rate_limit_type: r.rate_limit_type,
+                interval: r.interval,
+                interval_num: r.interval_num,
+                limit: r.limit,
+                count: 0,
+                started_at: 0,
+            })
+            .collect();
+
+        Self { mode, windows }
+    }
+
+    pub fn mode(&self) -> ThrottleMode {
+        self.mode
+    }
+
+    /// Count a request of `weight` if it fits into every window,
+    /// otherwise return the number of milliseconds to wait before trying again.
+    pub(crate) fn try_acquire_at(&mut self, weight: u64, now: Timestamp) -> Result<(), u64> {
+        let mut wait = 0;
+        for window in self.windows.iter_mut() {
+            window.roll(now);
+            let cost = window.cost(weight);
+            // A request heavier than the whole limit is let through on an empty window.
+            if window.count > 0 && window.count + cost > window.limit {
+                wait = wait.max(window.started_at + window.duration() - now);
+            }
+        }
+        if wait > 0 {
+            return Err(wait);
+        }
+
+        for window in self.windows.iter_mut() {
+            window.count += window.cost(weight);
+        }
+        Ok(())
+    }
+
+    /// Raise REQUEST_WEIGHT counters to the weight reported by Binance.
+    pub(crate) fn sync_at(&mut self, used_weight: &BTreeMap<RateLimitWindow, u64>, now: Timestamp) {
+        for window in self
+            .windows
+            .iter_mut()
+            .filter(|w| w.rate_limit_type == RateLimiter::RequestWeight)
+        {
+            let key = RateLimitWindow {
+                interval: window.interval,
+                interval_num: window.interval_num,
+            };
+            if let Some(used) = used_weight.get(&key) {
+                window.roll(now);
+                window.count = window.count.max(*used);
+            }
+        }
+    }
+}
+
+/// Documented request weight of an endpoint.
+///
+/// Weight of some endpoints depends on parameters: depth on `limit`,
+/// tickers and open orders on whether `symbol`/`symbols` are sent.
+pub fn request_weight(method: &Method, path: &Path, query: &str) -> u64 {
+    let params: Vec<(String, String)> = serde_urlencoded::from_str(query).unwrap_or_default();
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    };
+    // Number of requested symbols, None if all symbols are requested.
+    let symbols = || match (param("symbol"), param("symbols")) {
+        (Some(_), _) => Some(1),
+        (None, Some(symbols)) => Some(symbols.split(',').count() as u64),
+        (None, None) => None,
+    };
+
+    match path {
+        Path::Ping | Path::Time => 1,
+        Path::ExchangeInfo => 20,
+        Path::Depth => match param("limit").and_then(|l| l.parse::<u64>().ok()) {
+            None | Some(0..=100) => 5,
+            Some(101..=500) => 25,
+            Some(501..=1000) => 50,
+            Some(_) => 250,
+        },
+        Path::Trades | Path::HistoricalTrades => 25,
+        Path::AggTrades => 4,
+        Path::KLines | Path::UIKLines | Path::AvgPrice => 2,
+        Path::Ticker24hr => match symbols() {
+            Some(1..=20) => 2,
+            Some(21..=100) => 40,
+            _ => 80,
+        },
+        Path::TickerTradingDay | Path::Ticker => match symbols() {
+            Some(n) => (4 * n).min(200),
+            None => 200,
+        },
+        Path::TickerPrice | Path::TickerBook => match symbols() {
+            Some(_) => 2,
+            None => 4,
+        },
+        Path::Order if method == Method::GET => 4,
+        Path::OpenOrders if method == Method::GET => match symbols() {
+            Some(_) => 6,
+            None => 80,
+        },
+        Path::OrderTest | Path::SOROrderTest => match param("computeCommissionRates") {
+            Some("true") => 20,
+            _ => 1,
+        },
+        Path::OrderAmendKeepPriority => 4,
+        Path::OrderList if method == Method::GET => 4,
+        Path::OpenOrderList => 6,
+        Path::RateLimitOrder => 40,
+        Path::Account | Path::MyAllocations => 20,
+        Path::UserDataStream => 2,
+        Path::Order
+        | Path::OpenOrders
+        | Path::OrderCancelReplace
+        | Path::OrderListOCO
+        | Path::OrderListOTO
+        | Path::OrderListOTOCO
+        | Path::OrderList
+        | Path::SOROrder => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limits() -> Vec<RateLimit> {
+        vec![
+            RateLimit {
+                rate_limit_type: RateLimiter::RequestWeight,
+                interval: RateLimitInterval::Minute,
+                interval_num: 1,
+                limit: 6000,
+            },
+            RateLimit {
+                rate_limit_type: RateLimiter::Orders,
+                interval: RateLimitInterval::Second,
+                interval_num: 10,
+                limit: 50,
+            },
+            RateLimit {
+                rate_limit_type: RateLimiter::Orders,
+                interval: RateLimitInterval::Day,
+                interval_num: 1,
+                limit: 160000,
+            },
+        ]
+    }
+
+    #[test]
+    fn order_rate_budget_sync_and_record() {
+        let now = 1_700_000_000_000;
+        let mut budget = OrderRateBudget::new(&rate_limits());
+        budget.sync_at(
+            &[
+                UnfilledOrderCount {
+                    rate_limit_type: RateLimiter::Orders,
+                    interval: RateLimitInterval::Second,
+                    interval_num: 10,
+                    limit: 50,
+                    count: 45,
+                },
+                UnfilledOrderCount {
+                    rate_limit_type: RateLimiter::Orders,
+                    interval: RateLimitInterval::Day,
+                    interval_num: 1,
+                    limit: 160000,
+                    count: 1000,
+                },
+            ],
+            now,
+        );
+
+        assert_eq!(2, budget.windows().len());
+        assert_eq!(Some(5), budget.remaining_at(now));
+        assert!(budget.can_place_at(5, now));
+        assert!(!budget.can_place_at(6, now));
+
+        budget.record_at(5, now + 1);
+        assert!(!budget.can_place_at(1, now + 1));
+
+        // The next 10 seconds window starts from scratch, the daily one keeps counting.
+        let next = now + 10_000;
+        assert_eq!(Some(50), budget.remaining_at(next));
+        assert_eq!(1005, budget.windows()[1].count_at(next));
+    }
+
+    #[test]
+    fn order_rate_budget_sync_headers() {
+        let now = 1_700_000_000_000;
+        let mut budget = OrderRateBudget::new(&rate_limits());
+        let window = |interval, interval_num| RateLimitWindow {
+            interval,
+            interval_num,
+        };
+        let mut headers = Headers {
+            retry_after: None,
+            used_weight: BTreeMap::new(),
+            order_count: BTreeMap::from([
+                (window(RateLimitInterval::Second, 10), 48),
+                (window(RateLimitInterval::Day, 1), 1000),
+            ]),
+        };
+        budget.sync_headers_at(&headers, now);
+        assert_eq!(Some(2), budget.remaining_at(now));
+        assert_eq!(1000, budget.windows()[1].count_at(now));
+
+        // Windows without a header keep their count.
+        let next = now + 10_000;
+        headers.order_count = BTreeMap::from([(window(RateLimitInterval::Second, 10), 1)]);
+        budget.sync_headers_at(&headers, next);
+        assert_eq!(Some(49), budget.remaining_at(next));
+        assert_eq!(1000, budget.windows()[1].count_at(next));
+    }
+
+    #[test]
+    fn order_rate_budget_ignores_zero_length_windows() {
+        let now = 1_700_000_000_000;
+        let zero = RateLimit {
+            rate_limit_type: RateLimiter::Orders,
+            interval: RateLimitInterval::Second,
+            interval_num: 0,
+            limit: 50,
+        };
+        let mut budget = OrderRateBudget::new(&[zero]);
+        budget.sync_at(
+            &[UnfilledOrderCount {
+                rate_limit_type: RateLimiter::Orders,
+                interval: RateLimitInterval::Minute,
+                interval_num: 0,
+                limit: 50,
+                count: 1,
+            }],
+            now,
+        );
+        budget.record_at(1, now);
+
+        assert!(budget.windows().is_empty());
+        assert_eq!(None, budget.remaining_at(now));
+    }
+
+    #[test]
+    fn request_limiter_acquire_and_sync() {
+        let now = 1_700_000_100_000;
+        let mut limiter = RequestLimiter::new(
+            &[
+                RateLimit {
+                    rate_limit_type: RateLimiter::RequestWeight,
+                    interval: RateLimitInterval::Minute,
+                    interval_num: 1,
+                    limit: 100,
+                },
+                RateLimit {
+                    rate_limit_type: RateLimiter::RawRequests,
+                    interval: RateLimitInterval::Minute,
+                    interval_num: 5,
+                    limit: 3,
+                },
+            ],
+            ThrottleMode::Reject,
+        );
+
+        assert_eq!(Ok(()), limiter.try_acquire_at(50, now));
+        assert_eq!(Ok(()), limiter.try_acquire_at(50, now + 1_000));
+        assert_eq!(Err(59_000), limiter.try_acquire_at(1, now + 1_000));
+
+        // Next minute: weight resets, the third raw request still fits.
+        assert_eq!(Ok(()), limiter.try_acquire_at(1, now + 60_000));
+        // Another service on the same IP used the rest of the weight.
+        let used_weight = BTreeMap::from([(
+            RateLimitWindow {
+                interval: RateLimitInterval::Minute,
+                interval_num: 1,
+            },
+            100,
+        )]);
+        limiter.sync_at(&used_weight, now + 61_000);
+        assert_eq!(Err(239_000), limiter.try_acquire_at(1, now + 61_000));
+        // Raw requests are exhausted until the 5 minutes window ends.
+        assert_eq!(Err(180_000), limiter.try_acquire_at(1, now + 120_000));
+    }
+
+    #[test]
+    fn request_limiter_ignores_zero_length_windows() {
+        let now = 1_700_000_100_000;
+        let mut limiter = RequestLimiter::new(
+            &[RateLimit {
+                rate_limit_type: RateLimiter::RequestWeight,
+                interval: RateLimitInterval::Minute,
+                interval_num: 0,
+                limit: 100,
+            }],
+            ThrottleMode::Reject,
+        );
+        let used_weight = BTreeMap::from([(
+            RateLimitWindow {
+                interval: RateLimitInterval::Minute,
+                interval_num: 0,
+            },
+            100,
+        )]);
+        limiter.sync_at(&used_weight, now);
+
+        assert_eq!(Ok(()), limiter.try_acquire_at(1000, now));
+    }
+
+    #[test]
+    fn request_weight_depends_on_params() {
+        assert_eq!(
+            5,
+            request_weight(&Method::GET, &Path::Depth, "symbol=BTCUSDT")
+        );
+        assert_eq!(
+            250,
+            request_weight(&Method::GET, &Path::Depth, "symbol=BTCUSDT&limit=5000")
+        );
+        assert_eq!(
+            2,
+            request_weight(&Method::GET, &Path::Ticker24hr, "type=FULL&symbol=BTCUSDT")
+        );
+        assert_eq!(
+            80,
+            request_weight(&Method::GET, &Path::Ticker24hr, "type=FULL")
+        );
+        assert_eq!(
+            1,
+            request_weight(&Method::DELETE, &Path::OpenOrders, "symbol=BTCUSDT")
+        );
+        assert_eq!(80, request_weight(&Method::GET, &Path::OpenOrders, ""));
+    }
+
+    #[test]
+    fn parse_rate_limit_window() {
+        let expected = RateLimitWindow {
+            interval: RateLimitInterval::Second,
+            interval_num: 10,
+        };
+
+        assert_eq!(Ok(expected), "10s".parse());
+        assert!("1X".parse::<RateLimitWindow>().is_err());
+        assert!("".parse::<RateLimitWindow>().is_err());
+    }
+
+    #[test]
+    fn order_rate_budget_without_limits() {
+        let budget = OrderRateBudget::new(&[]);
+
+        assert_eq!(None, budget.remaining_at(0));
+        assert!(budget.can_place_at(1_000_000, 0));
+    }
+}
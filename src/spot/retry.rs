@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use super::{Error, ErrorCode};
+
+/// Retry policy for failed requests.
+///
+/// GET requests are retried on HTTP 418/429, 5xx, connection errors and
+/// `Disconnected`, `Timeout` and `ServerBusy` API errors.
+/// POST, PUT and DELETE requests are not retried unless `retry_non_idempotent` is set,
+/// since a retried order may be executed twice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt, 0 disables retries.
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled on every following retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the exponential backoff.
+    pub max_backoff: Duration,
+    /// Give up instead of waiting when `Retry-After` is longer than this, e.g. on IP bans.
+    pub max_retry_after: Duration,
+    /// Also retry POST, PUT and DELETE requests, e.g. order placement.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+            max_retry_after: Duration::from_secs(60),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy without retries.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before the next attempt or `None` when the request should not be retried.
+    ///
+    /// `attempts` is the number of attempts made so far,
+    /// `retry_after` is the `Retry-After` header value in seconds.
+    pub(crate) fn delay(
+        &self,
+        attempts: u32,
+        idempotent: bool,
+        error: &Error,
+        retry_after: Option<u64>,
+    ) -> Option<Duration> {
+        if attempts > self.max_retries || !(idempotent || self.retry_non_idempotent) {
+            return None;
+        }
+        if !is_retryable(error) {
+            return None;
+        }
+
+        match retry_after {
+            Some(seconds) => {
+                let wait = Duration::from_secs(seconds);
+                (wait <= self.max_retry_after).then_some(wait)
+            }
+            None => Some(self.backoff(attempts)),
+        }
+    }
+
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+fn is_retryable(error: &Error) -> bool {
+    if let Some(ErrorCode::Disconnected | ErrorCode::Timeout | ErrorCode::ServerBusy) = error.code()
+    {
+        return true;
+    }
+    if let Error::Reqwest(error) = error
+        && (error.is_connect() || error.is_timeout())
+    {
+        return true;
+    }
+
+    matches!(error.status(), Some(418 | 429 | 500..=599))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(status: u16, code: ErrorCode) -> Error {
+        Error::Api {
+            status,
+            code,
+            msg: String::new(),
+        }
+    }
+
+    #[test]
+    fn retry_delay() {
+        let policy = RetryPolicy::default();
+        let busy = api_error(503, ErrorCode::ServerBusy);
+        let too_many = api_error(429, ErrorCode::TooManyRequests);
+        let rejected = api_error(400, ErrorCode::InvalidMessage);
+
+        assert_eq!(
+            policy.delay(1, true, &busy, None),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(
+            policy.delay(3, true, &busy, None),
+            Some(Duration::from_millis(800))
+        );
+        assert_eq!(policy.delay(4, true, &busy, None), None);
+        assert_eq!(
+            policy.delay(1, true, &too_many, Some(5)),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(policy.delay(1, true, &too_many, Some(120)), None);
+        assert_eq!(policy.delay(1, true, &rejected, None), None);
+        assert_eq!(policy.delay(1, false, &busy, None), None);
+        assert_eq!(RetryPolicy::none().delay(1, true, &busy, None), None);
+    }
+}
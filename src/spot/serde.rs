@@ -1,4 +1,12 @@
-use serde::Deserialize;
+use std::fmt;
+
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{MapAccess, Visitor},
+};
+use serde_json::Value;
+
+use crate::spot::Error;
 
 pub fn deserialize_str<'de, T>(
     json: &'de str,
@@ -10,3 +18,59 @@ where
 
     serde_path_to_error::deserialize(deserializer)
 }
+
+/// Encode parameters as a REST query string. List parameters are sent as JSON arrays,
+/// e.g. `symbols=["BTCUSDT","BNBBTC"]`, other values as is.
+pub fn to_query<P>(params: &P) -> Result<String, Error>
+where
+    P: Serialize + ?Sized,
+{
+    let json = serde_json::to_string(params)?;
+    let Pairs(pairs) = serde_json::from_str(&json)?;
+    let pairs: Vec<(String, String)> = pairs
+        .into_iter()
+        .filter_map(|(name, value)| match value {
+            Value::Null => None,
+            Value::String(value) => Some((name, value)),
+            value => Some((name, value.to_string())),
+        })
+        .collect();
+    Ok(serde_urlencoded::to_string(pairs)?)
+}
+
+/// Fields of a JSON object in their serialization order.
+struct Pairs(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for Pairs {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PairsVisitor;
+
+        impl<'de> Visitor<'de> for PairsVisitor {
+            type Value = Pairs;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a struct of parameters")
+            }
+
+            fn visit_unit<E>(self) -> Result<Pairs, E> {
+                Ok(Pairs(Vec::new()))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Pairs, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut pairs = Vec::new();
+                while let Some(pair) = map.next_entry()? {
+                    pairs.push(pair);
+                }
+                Ok(Pairs(pairs))
+            }
+        }
+
+        deserializer.deserialize_any(PairsVisitor)
+    }
+}
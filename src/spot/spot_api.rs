@@ -0,0 +1,291 @@
+use std::future::Future;
+
+use crate::spot::{
+    AccountInformation, CancelAllOpenOrdersParams, CancelOrderParams, CanceledOpenOrder,
+    CanceledOrder, Client, CurrentAveragePrice, Error, ExchangeInfo, GetAccountInformationParams,
+    GetCurrentAveragePriceParams, GetExchangeInfoParams, GetKlineListParams, GetOpenOrdersParams,
+    GetOrderBookParams, GetRecentTradesParams, Kline, NewOrderParams, Order, OrderBook,
+    OrderResponse, QueryOrderParams, RecentTrade, ServerTime, TestNewOrder, TestNewOrderParams,
+    UnfilledOrderCount, ws_api::WsApiClient,
+};
+
+/// Market data, trading and account requests independent of the transport.
+///
+/// Implemented by the REST [`Client`] and the [`WsApiClient`], and can be implemented
+/// by a simulator for backtesting.
+pub trait SpotApi {
+    fn get_server_time(&self) -> impl Future<Output = Result<ServerTime, Error>> + Send;
+
+    fn get_exchange_info(
+        &self,
+        params: GetExchangeInfoParams,
+    ) -> impl Future<Output = Result<ExchangeInfo, Error>> + Send;
+
+    fn get_order_book(
+        &self,
+        params: GetOrderBookParams,
+    ) -> impl Future<Output = Result<OrderBook, Error>> + Send;
+
+    fn recent_trades_list(
+        &self,
+        params: GetRecentTradesParams,
+    ) -> impl Future<Output = Result<Vec<RecentTrade>, Error>> + Send;
+
+    fn get_kline_list(
+        &self,
+        params: GetKlineListParams,
+    ) -> impl Future<Output = Result<Vec<Kline>, Error>> + Send;
+
+    fn get_current_average_price(
+        &self,
+        params: GetCurrentAveragePriceParams,
+    ) -> impl Future<Output = Result<CurrentAveragePrice, Error>> + Send;
+
+    fn new_order(
+        &self,
+        params: NewOrderParams,
+    ) -> impl Future<Output = Result<OrderResponse, Error>> + Send;
+
+    fn test_new_order(
+        &self,
+        params: TestNewOrderParams,
+    ) -> impl Future<Output = Result<TestNewOrder, Error>> + Send;
+
+    fn query_order(
+        &self,
+        params: QueryOrderParams,
+    ) -> impl Future<Output = Result<Order, Error>> + Send;
+
+    fn cancel_order(
+        &self,
+        params: CancelOrderParams,
+    ) -> impl Future<Output = Result<CanceledOrder, Error>> + Send;
+
+    fn cancel_all_open_orders(
+        &self,
+        params: CancelAllOpenOrdersParams,
+    ) -> impl Future<Output = Result<Vec<CanceledOpenOrder>, Error>> + Send;
+
+    fn current_open_orders(
+        &self,
+        params: GetOpenOrdersParams,
+    ) -> impl Future<Output = Result<Vec<Order>, Error>> + Send;
+
+    fn get_unfilled_order_count(
+        &self,
+    ) -> impl Future<Output = Result<Vec<UnfilledOrderCount>, Error>> + Send;
+
+    fn get_account_information(
+        &self,
+        params: GetAccountInformationParams,
+    ) -> impl Future<Output = Result<AccountInformation, Error>> + Send;
+}
+
+impl SpotApi for Client {
+    async fn get_server_time(&self) -> Result<ServerTime, Error> {
+        Ok(Client::get_server_time(self).await?.result)
+    }
+
+    async fn get_exchange_info(
+        &self,
+        params: GetExchangeInfoParams,
+    ) -> Result<ExchangeInfo, Error> {
+        Ok(Client::get_exchange_info(self, params).await?.result)
+    }
+
+    async fn get_order_book(&self, params: GetOrderBookParams) -> Result<OrderBook, Error> {
+        Ok(Client::get_order_book(self, params).await?.result)
+    }
+
+    async fn recent_trades_list(
+        &self,
+        params: GetRecentTradesParams,
+    ) -> Result<Vec<RecentTrade>, Error> {
+        Ok(Client::recent_trades_list(self, params).await?.result)
+    }
+
+    async fn get_kline_list(&self, params: GetKlineListParams) -> Result<Vec<Kline>, Error> {
+        Ok(Client::get_kline_list(self, params).await?.result)
+    }
+
+    async fn get_current_average_price(
+        &self,
+        params: GetCurrentAveragePriceParams,
+    ) -> Result<CurrentAveragePrice, Error> {
+        Ok(Client::get_current_average_price(self, params)
+            .await?
+            .result)
+    }
+
+    async fn new_order(&self, params: NewOrderParams) -> Result<OrderResponse, Error> {
+        Ok(Client::new_order(self, params).await?.result)
+    }
+
+    async fn test_new_order(&self, params: TestNewOrderParams) -> Result<TestNewOrder, Error> {
+        Ok(Client::test_new_order(self, params).await?.result)
+    }
+
+    async fn query_order(&self, params: QueryOrderParams) -> Result<Order, Error> {
+        Ok(Client::query_order(self, params).await?.result)
+    }
+
+    async fn cancel_order(&self, params: CancelOrderParams) -> Result<CanceledOrder, Error> {
+        Ok(Client::cancel_order(self, params).await?.result)
+    }
+
+    async fn cancel_all_open_orders(
+        &self,
+        params: CancelAllOpenOrdersParams,
+    ) -> Result<Vec<CanceledOpenOrder>, Error> {
+        Ok(Client::cancel_all_open_orders(self, params).await?.result)
+    }
+
+    async fn current_open_orders(&self, params: GetOpenOrdersParams) -> Result<Vec<Order>, Error> {
+        Ok(Client::current_open_orders(self, params).await?.result)
+    }
+
+    async fn get_unfilled_order_count(&self) -> Result<Vec<UnfilledOrderCount>, Error> {
+        Ok(Client::get_unfilled_order_count(self).await?.result)
+    }
+
+    async fn get_account_information(
+        &self,
+        params: GetAccountInformationParams,
+    ) -> Result<AccountInformation, Error> {
+        Ok(Client::get_account_information(self, params).await?.result)
+    }
+}
+
+impl SpotApi for WsApiClient {
+    async fn get_server_time(&self) -> Result<ServerTime, Error> {
+        Ok(WsApiClient::get_server_time(self).await?.result)
+    }
+
+    async fn get_exchange_info(
+        &self,
+        params: GetExchangeInfoParams,
+    ) -> Result<ExchangeInfo, Error> {
+        Ok(WsApiClient::get_exchange_info(self, params).await?.result)
+    }
+
+    async fn get_order_book(&self, params: GetOrderBookParams) -> Result<OrderBook, Error> {
+        Ok(WsApiClient::get_order_book(self, params).await?.result)
+    }
+
+    async fn recent_trades_list(
+        &self,
+        params: GetRecentTradesParams,
+    ) -> Result<Vec<RecentTrade>, Error> {
+        Ok(WsApiClient::recent_trades_list(self, params).await?.result)
+    }
+
+    async fn get_kline_list(&self, params: GetKlineListParams) -> Result<Vec<Kline>, Error> {
+        Ok(WsApiClient::get_kline_list(self, params).await?.result)
+    }
+
+    async fn get_current_average_price(
+        &self,
+        params: GetCurrentAveragePriceParams,
+    ) -> Result<CurrentAveragePrice, Error> {
+        Ok(WsApiClient::get_current_average_price(self, params)
+            .await?
+            .result)
+    }
+
+    async fn new_order(&self, params: NewOrderParams) -> Result<OrderResponse, Error> {
+        Ok(WsApiClient::new_order(self, params).await?.result)
+    }
+
+    async fn test_new_order(&self, params: TestNewOrderParams) -> Result<TestNewOrder, Error> {
+        Ok(WsApiClient::test_new_order(self, params).await?.result)
+    }
+
+    async fn query_order(&self, params: QueryOrderParams) -> Result<Order, Error> {
+        Ok(WsApiClient::query_order(self, params).await?.result)
+    }
+
+    async fn cancel_order(&self, params: CancelOrderParams) -> Result<CanceledOrder, Error> {
+        Ok(WsApiClient::cancel_order(self, params).await?.result)
+    }
+
+    async fn cancel_all_open_orders(
+        &self,
+        params: CancelAllOpenOrdersParams,
+    ) -> Result<Vec<CanceledOpenOrder>, Error> {
+        Ok(WsApiClient::cancel_all_open_orders(self, params)
+            .await?
+            .result)
+    }
+
+    async fn current_open_orders(&self, params: GetOpenOrdersParams) -> Result<Vec<Order>, Error> {
+        Ok(WsApiClient::current_open_orders(self, params).await?.result)
+    }
+
+    async fn get_unfilled_order_count(&self) -> Result<Vec<UnfilledOrderCount>, Error> {
+        Ok(WsApiClient::get_unfilled_order_count(self).await?.result)
+    }
+
+    async fn get_account_information(
+        &self,
+        params: GetAccountInformationParams,
+    ) -> Result<AccountInformation, Error> {
+        Ok(WsApiClient::get_account_information(self, params)
+            .await?
+            .result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use reqwest::Method;
+    use rust_decimal::{Decimal, dec};
+
+    use super::*;
+    use crate::spot::{
+        CannedTransport, ClientConfig, HttpResponse,
+        ws_api::{WsApiConfig, test_server::server},
+    };
+
+    async fn average_price(api: &impl SpotApi, symbol: &str) -> Result<Decimal, Error> {
+        let params = GetCurrentAveragePriceParams {
+            symbol: symbol.to_string(),
+        };
+        Ok(api.get_current_average_price(params).await?.price)
+    }
+
+    #[tokio::test]
+    async fn generic_over_transport() {
+        let transport = Arc::new(CannedTransport::new());
+        transport.push(
+            Method::GET,
+            "/api/v3/avgPrice",
+            HttpResponse::json(
+                200,
+                r#"{"mins":5,"price":"9.35751834","closeTime":1694061154503}"#,
+            ),
+        );
+        let cfg = ClientConfig {
+            transport: Some(transport),
+            ..Default::default()
+        };
+        let client = Client::new(cfg).unwrap();
+
+        let price = average_price(&client, "BNBUSDT").await.unwrap();
+        assert_eq!(price, dec!(9.35751834));
+    }
+
+    #[tokio::test]
+    async fn generic_over_ws_api() {
+        let client = WsApiClient::connect(WsApiConfig {
+            base_url: server("secret".into()).await,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+        let price = average_price(&client, "BNBUSDT").await.unwrap();
+        assert_eq!(price, dec!(9.35751834));
+    }
+}
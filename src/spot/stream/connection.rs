@@ -0,0 +1,420 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    time::Duration,
+};
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tokio::{net::TcpStream, time::Instant};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message};
+
+use crate::spot::{Error, serde::deserialize_str};
+
+use super::{Stream, StreamConfig, StreamEvent};
+
+/// Single stream connection. Server pings are answered automatically while reading, so
+/// [`StreamConnection::next`] has to be polled regularly for the connection to stay open.
+///
+/// Control requests wait for their acknowledgement, events received meanwhile are
+/// returned by following [`StreamConnection::next`] calls.
+pub struct StreamConnection {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    mode: Mode,
+    subscriptions: BTreeSet<String>,
+    pending: VecDeque<Result<StreamEvent, Error>>,
+    next_id: u64,
+    limiter: MessageLimiter,
+    max_streams: usize,
+    request_timeout: Duration,
+    silence_timeout: Duration,
+    connected_at: Instant,
+    last_message_at: Instant,
+    alive: bool,
+}
+
+/// Payload format of events.
+enum Mode {
+    /// `/ws` connection, events are not wrapped. Holds the name of the only stream, if any.
+    Raw(Option<String>),
+    /// `/stream` connection or `combined` property set, events are wrapped with the stream name.
+    Combined,
+}
+
+/// Response to a control request.
+#[derive(Deserialize)]
+struct ControlResponse {
+    id: u64,
+    #[serde(default)]
+    result: Value,
+    code: Option<i64>,
+    msg: Option<String>,
+}
+
+impl StreamConnection {
+    /// Connect to a single raw stream (`/ws/<stream>`), to combined streams (`/stream?streams=`)
+    /// or without streams (`/ws`) to subscribe later.
+    pub(super) async fn connect(cfg: &StreamConfig, streams: &[Stream]) -> Result<Self, Error> {
+        let names: Vec<String> = streams.iter().map(Stream::to_string).collect();
+        if names.len() > cfg.max_streams_per_connection {
+            return Err(stream_limit(cfg.max_streams_per_connection));
+        }
+        let (url, mode) = match names.as_slice() {
+            [] => (format!("{}/ws", cfg.base_url), Mode::Raw(None)),
+            [name] => (
+                format!("{}/ws/{name}", cfg.base_url),
+                Mode::Raw(Some(name.clone())),
+            ),
+            _ => {
+                let url = format!("{}/stream?streams={}", cfg.base_url, names.join("/"));
+                (url, Mode::Combined)
+            }
+        };
+
+        Self::open(cfg, url, mode, names).await
+    }
+
+    /// Connect to `/ws/<path>`, e.g. a user data stream listen key.
+    pub(super) async fn connect_raw(cfg: &StreamConfig, path: &str) -> Result<Self, Error> {
+        let url = format!("{}/ws/{path}", cfg.base_url);
+        Self::open(cfg, url, Mode::Raw(None), Vec::new()).await
+    }
+
+    async fn open(
+        cfg: &StreamConfig,
+        url: String,
+        mode: Mode,
+        names: Vec<String>,
+    ) -> Result<Self, Error> {
+        let limiter = MessageLimiter::new(cfg.max_messages_per_second)?;
+        let (socket, _) = connect_async(url).await?;
+        let now = Instant::now();
+        Ok(Self {
+            socket,
+            mode,
+            subscriptions: names.into_iter().collect(),
+            pending: VecDeque::new(),
+            next_id: 1,
+            limiter,
+            max_streams: cfg.max_streams_per_connection,
+            request_timeout: cfg.request_timeout,
+            silence_timeout: cfg.silence_timeout,
+            connected_at: now,
+            last_message_at: now,
+            alive: true,
+        })
+    }
+
+    /// Next event, `None` once the connection is closed.
+    pub async fn next(&mut self) -> Option<Result<StreamEvent, Error>> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(event);
+        }
+        loop {
+            let text = match self.read().await? {
+                Ok(text) => text,
+                Err(error) => return Some(Err(error)),
+            };
+            // Late acknowledgements of timed out requests are dropped.
+            if deserialize_str::<ControlResponse>(&text).is_err() {
+                return Some(self.decode(&text));
+            }
+        }
+    }
+
+    /// Whether the connection is still usable: it was not closed, failed or silent
+    /// for longer than the silence timeout.
+    pub fn is_alive(&self) -> bool {
+        self.alive
+    }
+
+    pub(super) fn connected_at(&self) -> Instant {
+        self.connected_at
+    }
+
+    /// When the connection is considered dead unless a message, including a ping, is received.
+    pub(super) fn silent_at(&self) -> Instant {
+        self.last_message_at + self.silence_timeout
+    }
+
+    /// Streams this connection is subscribed to, as tracked by the client.
+    pub fn subscriptions(&self) -> &BTreeSet<String> {
+        &self.subscriptions
+    }
+
+    /// Number of streams that can still be subscribed on this connection.
+    pub fn capacity(&self) -> usize {
+        self.max_streams.saturating_sub(self.subscriptions.len())
+    }
+
+    /// Subscribe to streams, fails without sending when the connection stream limit would be exceeded.
+    pub async fn subscribe(&mut self, streams: &[Stream]) -> Result<(), Error> {
+        let names = streams.iter().map(Stream::to_string).collect();
+        self.subscribe_names(names).await
+    }
+
+    pub(super) async fn subscribe_names(&mut self, mut names: Vec<String>) -> Result<(), Error> {
+        names.retain(|name| !self.subscriptions.contains(name));
+        if names.is_empty() {
+            return Ok(());
+        }
+        if names.len() > self.capacity() {
+            return Err(stream_limit(self.max_streams));
+        }
+        if !matches!(self.mode, Mode::Combined) {
+            self.set_combined(true).await?;
+        }
+
+        self.request("SUBSCRIBE", json!(names)).await?;
+        self.subscriptions.extend(names);
+        Ok(())
+    }
+
+    pub async fn unsubscribe(&mut self, streams: &[Stream]) -> Result<(), Error> {
+        let names: Vec<String> = streams.iter().map(Stream::to_string).collect();
+        if names.is_empty() {
+            return Ok(());
+        }
+
+        self.request("UNSUBSCRIBE", json!(names)).await?;
+        for name in &names {
+            self.subscriptions.remove(name);
+        }
+        Ok(())
+    }
+
+    /// Streams the server reports as subscribed.
+    pub async fn list_subscriptions(&mut self) -> Result<Vec<Stream>, Error> {
+        let result = self.request("LIST_SUBSCRIPTIONS", Value::Null).await?;
+        let names: Vec<String> = serde_json::from_value(result)?;
+        names.iter().map(|name| name.parse()).collect()
+    }
+
+    /// Set the `combined` property: whether events are wrapped with their stream name.
+    pub async fn set_combined(&mut self, combined: bool) -> Result<(), Error> {
+        self.request("SET_PROPERTY", json!(["combined", combined]))
+            .await?;
+        self.mode = match combined {
+            true => Mode::Combined,
+            false => Mode::Raw(None),
+        };
+        Ok(())
+    }
+
+    pub async fn close(mut self) -> Result<(), Error> {
+        self.socket.close(None).await?;
+        Ok(())
+    }
+
+    /// Close the connection, returning the events received until the server acknowledged the close.
+    pub(super) async fn drain(mut self) -> Vec<Result<StreamEvent, Error>> {
+        let mut events: Vec<_> = self.pending.drain(..).collect();
+        if self.socket.close(None).await.is_err() {
+            return events;
+        }
+        let deadline = Instant::now() + self.request_timeout;
+        while let Ok(Some(Ok(text))) = tokio::time::timeout_at(deadline, self.read()).await {
+            if deserialize_str::<ControlResponse>(&text).is_err() {
+                events.push(self.decode(&text));
+            }
+        }
+        events
+    }
+
+    /// Send control request and wait for its acknowledgement.
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value, Error> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut request = json!({ "method": method, "id": id });
+        if !params.is_null() {
+            request["params"] = params;
+        }
+
+        self.limiter.acquire().await;
+        self.socket.send(Message::text(request.to_string())).await?;
+
+        let deadline = Instant::now() + self.request_timeout;
+        loop {
+            let text = match tokio::time::timeout_at(deadline, self.read()).await {
+                Ok(Some(text)) => text?,
+                Ok(None) => return Err(Error::from("stream connection closed")),
+                Err(_) => return Err(Error::Msg(format!("{method} request timed out"))),
+            };
+            match deserialize_str::<ControlResponse>(&text) {
+                Ok(response) if response.id == id => {
+                    return match response.code {
+                        Some(code) => Err(Error::StreamRequest {
+                            code,
+                            msg: response.msg.unwrap_or_default(),
+                        }),
+                        None => Ok(response.result),
+                    };
+                }
+                Ok(_) => continue,
+                Err(_) => {
+                    let event = self.decode(&text);
+                    self.pending.push_back(event);
+                }
+            }
+        }
+    }
+
+    /// Read next text message, `None` once the connection is closed.
+    ///
+    /// The silence timeout counts from the last received message, so dropping and
+    /// restarting reads does not extend it.
+    pub(super) async fn read(&mut self) -> Option<Result<String, Error>> {
+        loop {
+            let message = match tokio::time::timeout_at(self.silent_at(), self.socket.next()).await
+            {
+                Ok(Some(Ok(message))) => {
+                    self.last_message_at = Instant::now();
+                    message
+                }
+                Ok(Some(Err(error))) => {
+                    self.alive = false;
+                    return Some(Err(error.into()));
+                }
+                Ok(None) => {
+                    self.alive = false;
+                    return None;
+                }
+                Err(_) => {
+                    self.alive = false;
+                    let msg = format!("no message received for {:?}", self.silence_timeout);
+                    return Some(Err(Error::Msg(msg)));
+                }
+            };
+            match message {
+                Message::Text(text) => return Some(Ok(text.to_string())),
+                Message::Close(_) => {
+                    self.alive = false;
+                    return None;
+                }
+                // Answered with a pong by tungstenite.
+                Message::Ping(_) => self.limiter.record(),
+                _ => continue,
+            }
+        }
+    }
+
+    fn decode(&self, text: &str) -> Result<StreamEvent, Error> {
+        let event = match &self.mode {
+            Mode::Raw(stream) => StreamEvent {
+                stream: stream.clone().unwrap_or_default(),
+                event: deserialize_str(text)?,
+            },
+            Mode::Combined => deserialize_str(text)?,
+        };
+        Ok(event)
+    }
+}
+
+fn stream_limit(max_streams: usize) -> Error {
+    Error::Msg(format!(
+        "a stream connection can listen to at most {max_streams} streams"
+    ))
+}
+
+/// Limits incoming messages per connection: Binance drops connections sending more than
+/// 5 messages (control requests, pings and pongs) per second.
+struct MessageLimiter {
+    limit: usize,
+    sent: VecDeque<Instant>,
+}
+
+impl MessageLimiter {
+    fn new(limit: usize) -> Result<Self, Error> {
+        if limit == 0 {
+            return Err(Error::from("max_messages_per_second must be at least 1"));
+        }
+        Ok(Self {
+            limit,
+            sent: VecDeque::new(),
+        })
+    }
+
+    async fn acquire(&mut self) {
+        if let Some(wait) = self.wait_at(Instant::now()) {
+            tokio::time::sleep(wait).await;
+        }
+        self.record();
+    }
+
+    /// Count a message sent without waiting, e.g. a pong.
+    fn record(&mut self) {
+        self.sent.push_back(Instant::now());
+    }
+
+    /// Time to wait until another message can be sent at `now`.
+    fn wait_at(&mut self, now: Instant) -> Option<Duration> {
+        let window = Duration::from_secs(1);
+        while self
+            .sent
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= window)
+        {
+            self.sent.pop_front();
+        }
+        if self.sent.len() < self.limit {
+            return None;
+        }
+        self.sent
+            .front()
+            .map(|sent| window - now.duration_since(*sent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_limiter() {
+        assert!(MessageLimiter::new(0).is_err());
+        let mut limiter = MessageLimiter::new(2).unwrap();
+        let start = Instant::now();
+
+        assert_eq!(limiter.wait_at(start), None);
+        limiter.sent.push_back(start);
+        assert_eq!(limiter.wait_at(start), None);
+        limiter.sent.push_back(start + Duration::from_millis(400));
+        assert_eq!(
+            limiter.wait_at(start + Duration::from_millis(600)),
+            Some(Duration::from_millis(400))
+        );
+        assert_eq!(limiter.wait_at(start + Duration::from_secs(1)), None);
+        assert_eq!(limiter.sent.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn pongs_count_against_message_limit() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            socket.send(Message::Ping("1".into())).await.unwrap();
+            socket.send(Message::Ping("2".into())).await.unwrap();
+            let message = r#"{"e":"trade","E":1,"s":"BTCUSDT","t":1,"p":"1","q":"1","T":1,"m":true,"M":true}"#;
+            socket.send(Message::text(message)).await.unwrap();
+            while socket.next().await.is_some() {}
+        });
+
+        let cfg = StreamConfig {
+            base_url: format!("ws://{addr}"),
+            max_messages_per_second: 0,
+            ..Default::default()
+        };
+        assert!(StreamConnection::connect(&cfg, &[]).await.is_err());
+
+        let cfg = StreamConfig {
+            max_messages_per_second: 2,
+            ..cfg
+        };
+        let mut connection = StreamConnection::connect(&cfg, &[]).await.unwrap();
+        connection.next().await.unwrap().unwrap();
+        assert_eq!(connection.limiter.sent.len(), 2);
+        assert!(connection.limiter.wait_at(Instant::now()).is_some());
+    }
+}
@@ -0,0 +1,326 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer};
+
+use crate::spot::{KlineInterval, OrderLevel, Timestamp};
+
+/// Market data event.
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    AggTrade(AggTradeEvent),
+    Trade(TradeEvent),
+    Kline(KlineEvent),
+    MiniTicker(MiniTickerEvent),
+    Ticker(TickerEvent),
+    BookTicker(BookTickerEvent),
+    DepthUpdate(DepthUpdateEvent),
+}
+
+/// Events with an `e` event type field. `bookTicker` events have none.
+#[derive(Deserialize)]
+#[serde(tag = "e")]
+enum TaggedEvent {
+    #[serde(rename = "aggTrade")]
+    AggTrade(AggTradeEvent),
+    #[serde(rename = "trade")]
+    Trade(TradeEvent),
+    #[serde(rename = "kline")]
+    Kline(KlineEvent),
+    #[serde(rename = "24hrMiniTicker")]
+    MiniTicker(MiniTickerEvent),
+    #[serde(rename = "24hrTicker")]
+    Ticker(TickerEvent),
+    #[serde(rename = "depthUpdate")]
+    DepthUpdate(DepthUpdateEvent),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawEvent {
+    Tagged(Box<TaggedEvent>),
+    BookTicker(BookTickerEvent),
+}
+
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let event = match RawEvent::deserialize(deserializer)? {
+            RawEvent::Tagged(event) => match *event {
+                TaggedEvent::AggTrade(event) => Event::AggTrade(event),
+                TaggedEvent::Trade(event) => Event::Trade(event),
+                TaggedEvent::Kline(event) => Event::Kline(event),
+                TaggedEvent::MiniTicker(event) => Event::MiniTicker(event),
+                TaggedEvent::Ticker(event) => Event::Ticker(event),
+                TaggedEvent::DepthUpdate(event) => Event::DepthUpdate(event),
+            },
+            RawEvent::BookTicker(event) => Event::BookTicker(event),
+        };
+        Ok(event)
+    }
+}
+
+/// `<symbol>@aggTrade`: trades aggregated by taker order and price.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct AggTradeEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "a")]
+    pub id: i64,
+    #[serde(rename = "p")]
+    pub price: Decimal,
+    #[serde(rename = "q")]
+    pub qty: Decimal,
+    #[serde(rename = "f")]
+    pub first_trade_id: i64,
+    #[serde(rename = "l")]
+    pub last_trade_id: i64,
+    #[serde(rename = "T")]
+    pub trade_time: Timestamp,
+    #[serde(rename = "m")]
+    pub is_buyer_maker: bool,
+}
+
+/// `<symbol>@trade`: raw trade.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct TradeEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "t")]
+    pub id: i64,
+    #[serde(rename = "p")]
+    pub price: Decimal,
+    #[serde(rename = "q")]
+    pub qty: Decimal,
+    #[serde(rename = "T")]
+    pub trade_time: Timestamp,
+    #[serde(rename = "m")]
+    pub is_buyer_maker: bool,
+}
+
+/// `<symbol>@kline_<interval>`: current kline, pushed every second for 1s klines and every 2 seconds otherwise.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct KlineEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "k")]
+    pub kline: StreamKline,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct StreamKline {
+    #[serde(rename = "t")]
+    pub open_time: Timestamp,
+    #[serde(rename = "T")]
+    pub close_time: Timestamp,
+    #[serde(rename = "i")]
+    pub interval: KlineInterval,
+    /// First trade id, -1 if the kline has no trades.
+    #[serde(rename = "f")]
+    pub first_trade_id: i64,
+    /// Last trade id, -1 if the kline has no trades.
+    #[serde(rename = "L")]
+    pub last_trade_id: i64,
+    #[serde(rename = "o")]
+    pub open: Decimal,
+    #[serde(rename = "c")]
+    pub close: Decimal,
+    #[serde(rename = "h")]
+    pub high: Decimal,
+    #[serde(rename = "l")]
+    pub low: Decimal,
+    /// Base asset volume.
+    #[serde(rename = "v")]
+    pub volume: Decimal,
+    #[serde(rename = "n")]
+    pub trades: u64,
+    /// Whether the kline is closed.
+    #[serde(rename = "x")]
+    pub is_closed: bool,
+    #[serde(rename = "q")]
+    pub quote_volume: Decimal,
+    #[serde(rename = "V")]
+    pub taker_buy_base_volume: Decimal,
+    #[serde(rename = "Q")]
+    pub taker_buy_quote_volume: Decimal,
+}
+
+/// `<symbol>@miniTicker`: rolling 24h statistics, pushed every second.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct MiniTickerEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub close: Decimal,
+    #[serde(rename = "o")]
+    pub open: Decimal,
+    #[serde(rename = "h")]
+    pub high: Decimal,
+    #[serde(rename = "l")]
+    pub low: Decimal,
+    #[serde(rename = "v")]
+    pub volume: Decimal,
+    #[serde(rename = "q")]
+    pub quote_volume: Decimal,
+}
+
+/// `<symbol>@ticker`: rolling 24h statistics with best bid and ask, pushed every second.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct TickerEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p")]
+    pub price_change: Decimal,
+    #[serde(rename = "P")]
+    pub price_change_percent: Decimal,
+    #[serde(rename = "w")]
+    pub weighted_avg_price: Decimal,
+    /// Last price before the 24h window.
+    #[serde(rename = "x")]
+    pub first_trade_price: Decimal,
+    #[serde(rename = "c")]
+    pub last_price: Decimal,
+    #[serde(rename = "Q")]
+    pub last_qty: Decimal,
+    #[serde(rename = "b")]
+    pub bid_price: Decimal,
+    #[serde(rename = "B")]
+    pub bid_qty: Decimal,
+    #[serde(rename = "a")]
+    pub ask_price: Decimal,
+    #[serde(rename = "A")]
+    pub ask_qty: Decimal,
+    #[serde(rename = "o")]
+    pub open_price: Decimal,
+    #[serde(rename = "h")]
+    pub high_price: Decimal,
+    #[serde(rename = "l")]
+    pub low_price: Decimal,
+    #[serde(rename = "v")]
+    pub volume: Decimal,
+    #[serde(rename = "q")]
+    pub quote_volume: Decimal,
+    #[serde(rename = "O")]
+    pub open_time: Timestamp,
+    #[serde(rename = "C")]
+    pub close_time: Timestamp,
+    #[serde(rename = "F")]
+    pub first_id: i64,
+    #[serde(rename = "L")]
+    pub last_id: i64,
+    #[serde(rename = "n")]
+    pub count: u64,
+}
+
+/// `<symbol>@bookTicker`: best bid and ask, pushed in real time.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct BookTickerEvent {
+    #[serde(rename = "u")]
+    pub update_id: i64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "b")]
+    pub bid_price: Decimal,
+    #[serde(rename = "B")]
+    pub bid_qty: Decimal,
+    #[serde(rename = "a")]
+    pub ask_price: Decimal,
+    #[serde(rename = "A")]
+    pub ask_qty: Decimal,
+}
+
+/// `<symbol>@depth` and `<symbol>@depth@100ms`: order book changes, a quantity of 0 removes the level.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct DepthUpdateEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "U")]
+    pub first_update_id: i64,
+    #[serde(rename = "u")]
+    pub final_update_id: i64,
+    #[serde(rename = "b")]
+    pub bids: Vec<OrderLevel>,
+    #[serde(rename = "a")]
+    pub asks: Vec<OrderLevel>,
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+    use crate::spot::serde::deserialize_str;
+
+    #[test]
+    fn deserialize_kline_event() {
+        let json = r#"{
+            "e": "kline",
+            "E": 1672515782136,
+            "s": "BNBBTC",
+            "k": {
+                "t": 1672515780000,
+                "T": 1672515839999,
+                "s": "BNBBTC",
+                "i": "1m",
+                "f": 100,
+                "L": 200,
+                "o": "0.0010",
+                "c": "0.0020",
+                "h": "0.0025",
+                "l": "0.0015",
+                "v": "1000",
+                "n": 100,
+                "x": false,
+                "q": "1.0000",
+                "V": "500",
+                "Q": "0.500",
+                "B": "123456"
+            }
+        }"#;
+
+        let event: Event = deserialize_str(json).unwrap();
+
+        let Event::Kline(event) = event else {
+            panic!("expected kline event");
+        };
+        assert_eq!(event.kline.interval, KlineInterval::Minute1);
+        assert_eq!(event.kline.close, dec!(0.0020));
+        assert!(!event.kline.is_closed);
+    }
+
+    #[test]
+    fn deserialize_book_ticker_event() {
+        let json = r#"{
+            "u": 400900217,
+            "s": "BNBUSDT",
+            "b": "25.35190000",
+            "B": "31.21000000",
+            "a": "25.36520000",
+            "A": "40.66000000"
+        }"#;
+        let expected = Event::BookTicker(BookTickerEvent {
+            update_id: 400900217,
+            symbol: String::from("BNBUSDT"),
+            bid_price: dec!(25.35190000),
+            bid_qty: dec!(31.21000000),
+            ask_price: dec!(25.36520000),
+            ask_qty: dec!(40.66000000),
+        });
+
+        let current: Event = deserialize_str(json).unwrap();
+
+        assert_eq!(expected, current);
+    }
+}
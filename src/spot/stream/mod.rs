@@ -0,0 +1,539 @@
+//! WebSocket market data streams.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), binance::spot::Error> {
+//! use binance::spot::{KlineInterval, stream::{Stream, StreamClient, StreamConfig}};
+//!
+//! let client = StreamClient::new(StreamConfig::default());
+//! let streams = [
+//!     Stream::Trade { symbol: String::from("BTCUSDT") },
+//!     Stream::Kline { symbol: String::from("BTCUSDT"), interval: KlineInterval::Minute1 },
+//! ];
+//! let mut connection = client.connect(&streams).await?;
+//! while let Some(event) = connection.next().await {
+//!     println!("{:?}", event?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`MarketStreams`] subscribes and unsubscribes on open connections and opens further
+//! connections once the per connection stream limit is reached. It reconnects and
+//! resubscribes lost connections and replaces connections before Binance closes them
+//! after 24 hours, reporting this with [`StreamMessage`]s.
+
+mod connection;
+mod event;
+mod shards;
+mod user_data;
+
+pub use connection::*;
+pub use event::*;
+pub use shards::*;
+pub use user_data::*;
+
+use std::{str::FromStr, time::Duration};
+
+use serde::Deserialize;
+
+use crate::spot::{BASE_URL_STREAM_DATA1, Error, KlineInterval};
+
+/// Market data stream of a symbol.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Stream {
+    AggTrade {
+        symbol: String,
+    },
+    Trade {
+        symbol: String,
+    },
+    Kline {
+        symbol: String,
+        interval: KlineInterval,
+    },
+    MiniTicker {
+        symbol: String,
+    },
+    Ticker {
+        symbol: String,
+    },
+    BookTicker {
+        symbol: String,
+    },
+    /// Diff depth pushed every 1000ms.
+    Depth {
+        symbol: String,
+    },
+    /// Diff depth pushed every 100ms.
+    Depth100ms {
+        symbol: String,
+    },
+}
+
+impl std::fmt::Display for Stream {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::AggTrade { symbol } => write!(f, "{}@aggTrade", symbol.to_lowercase()),
+            Self::Trade { symbol } => write!(f, "{}@trade", symbol.to_lowercase()),
+            Self::Kline { symbol, interval } => {
+                write!(f, "{}@kline_{interval}", symbol.to_lowercase())
+            }
+            Self::MiniTicker { symbol } => write!(f, "{}@miniTicker", symbol.to_lowercase()),
+            Self::Ticker { symbol } => write!(f, "{}@ticker", symbol.to_lowercase()),
+            Self::BookTicker { symbol } => write!(f, "{}@bookTicker", symbol.to_lowercase()),
+            Self::Depth { symbol } => write!(f, "{}@depth", symbol.to_lowercase()),
+            Self::Depth100ms { symbol } => write!(f, "{}@depth@100ms", symbol.to_lowercase()),
+        }
+    }
+}
+
+impl FromStr for Stream {
+    type Err = Error;
+
+    /// Parse a stream name, e.g. `btcusdt@kline_1m`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let unknown = || Error::Msg(format!("unknown stream: {name}"));
+        let (symbol, kind) = name.split_once('@').ok_or_else(unknown)?;
+        let symbol = symbol.to_uppercase();
+        let stream = match kind {
+            "aggTrade" => Self::AggTrade { symbol },
+            "trade" => Self::Trade { symbol },
+            "miniTicker" => Self::MiniTicker { symbol },
+            "ticker" => Self::Ticker { symbol },
+            "bookTicker" => Self::BookTicker { symbol },
+            "depth" => Self::Depth { symbol },
+            "depth@100ms" => Self::Depth100ms { symbol },
+            kind => {
+                let interval = kind.strip_prefix("kline_").ok_or_else(unknown)?;
+                let interval = serde_json::Value::from(interval);
+                let interval = serde_json::from_value(interval).map_err(|_| unknown())?;
+                Self::Kline { symbol, interval }
+            }
+        };
+        Ok(stream)
+    }
+}
+
+/// Event with the name of the stream it was received from, e.g. `btcusdt@trade`.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct StreamEvent {
+    pub stream: String,
+    #[serde(rename = "data")]
+    pub event: Event,
+}
+
+#[derive(Clone)]
+pub struct StreamConfig {
+    pub base_url: String,
+    /// How long to wait for the acknowledgement of a control request.
+    pub request_timeout: Duration,
+    /// Binance allows 1024 streams per connection.
+    pub max_streams_per_connection: usize,
+    /// Binance allows 5 incoming messages per second per connection, pongs included. At least 1.
+    pub max_messages_per_second: usize,
+    /// A connection without any message, including pings, for this long is considered dead.
+    /// Binance pings every 20 seconds.
+    pub silence_timeout: Duration,
+    /// Binance closes connections after 24 hours, [`MarketStreams`] replaces them earlier.
+    pub rotate_after: Duration,
+    /// Delay before the second reconnect attempt, doubled after each failed attempt.
+    pub reconnect_backoff: Duration,
+    pub max_reconnect_backoff: Duration,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            base_url: BASE_URL_STREAM_DATA1.to_string(),
+            request_timeout: Duration::from_secs(10),
+            max_streams_per_connection: 1024,
+            max_messages_per_second: 5,
+            silence_timeout: Duration::from_secs(60),
+            rotate_after: Duration::from_secs(23 * 60 * 60),
+            reconnect_backoff: Duration::from_secs(1),
+            max_reconnect_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl StreamConfig {
+    /// Delay before reconnect attempt `attempt`: the first attempt is immediate,
+    /// then the backoff doubles up to the maximum.
+    pub(crate) fn reconnect_delay(&self, attempt: u32) -> Duration {
+        if attempt <= 1 {
+            return Duration::ZERO;
+        }
+        2u32.checked_pow(attempt - 2)
+            .and_then(|factor| self.reconnect_backoff.checked_mul(factor))
+            .unwrap_or(self.max_reconnect_backoff)
+            .min(self.max_reconnect_backoff)
+    }
+}
+
+pub struct StreamClient {
+    cfg: StreamConfig,
+}
+
+impl StreamClient {
+    pub fn new(cfg: StreamConfig) -> Self {
+        Self { cfg }
+    }
+
+    /// Connect to a single raw stream (`/ws/<stream>`), to combined streams (`/stream?streams=`)
+    /// or without streams (`/ws`) to subscribe later.
+    pub async fn connect(&self, streams: &[Stream]) -> Result<StreamConnection, Error> {
+        StreamConnection::connect(&self.cfg, streams).await
+    }
+
+    /// Streams subscribed over as many connections as the per connection limit requires.
+    pub fn market_streams(&self) -> MarketStreams {
+        MarketStreams::new(self.cfg.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use futures_util::{SinkExt, StreamExt};
+    use serde_json::{Value, json};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_tungstenite::{
+        accept_async, accept_hdr_async,
+        tungstenite::{
+            Message,
+            handshake::server::{Request, Response},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn stream_names() {
+        let symbol = String::from("BTCUSDT");
+        let kline = Stream::Kline {
+            symbol: symbol.clone(),
+            interval: KlineInterval::Hour1,
+        };
+        assert_eq!(kline.to_string(), "btcusdt@kline_1h");
+        assert_eq!(
+            Stream::Depth100ms { symbol }.to_string(),
+            "btcusdt@depth@100ms"
+        );
+
+        for name in [
+            "btcusdt@kline_1h",
+            "btcusdt@depth@100ms",
+            "ethbtc@bookTicker",
+        ] {
+            assert_eq!(name.parse::<Stream>().unwrap().to_string(), name);
+        }
+        assert!("btcusdt@kline_2m".parse::<Stream>().is_err());
+        assert!("btcusdt".parse::<Stream>().is_err());
+    }
+
+    #[allow(clippy::result_large_err)]
+    #[tokio::test]
+    async fn combined_streams() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut uri = String::new();
+            let callback = |request: &Request, response: Response| {
+                uri = request.uri().to_string();
+                Ok(response)
+            };
+            let mut socket = accept_hdr_async(stream, callback).await.unwrap();
+            let message = r#"{"stream":"btcusdt@trade","data":{"e":"trade","E":1,"s":"BTCUSDT","t":12345,"p":"0.001","q":"100","T":1,"m":true,"M":true}}"#;
+            socket.send(Message::text(message)).await.unwrap();
+            socket.close(None).await.unwrap();
+            uri
+        });
+
+        let cfg = StreamConfig {
+            base_url: format!("ws://{addr}"),
+            ..Default::default()
+        };
+        let streams = [
+            Stream::Trade {
+                symbol: String::from("BTCUSDT"),
+            },
+            Stream::BookTicker {
+                symbol: String::from("BTCUSDT"),
+            },
+        ];
+        let mut connection = StreamClient::new(cfg).connect(&streams).await.unwrap();
+
+        let event = connection.next().await.unwrap().unwrap();
+        assert_eq!(event.stream, "btcusdt@trade");
+        assert!(matches!(
+            event.event,
+            Event::Trade(TradeEvent { id: 12345, .. })
+        ));
+        assert!(connection.next().await.is_none());
+        assert_eq!(
+            server.await.unwrap(),
+            "/stream?streams=btcusdt@trade/btcusdt@bookTicker"
+        );
+    }
+
+    /// Serve control requests, sending a trade event for each new subscription before the ack.
+    async fn serve_control(stream: TcpStream) {
+        let mut socket = accept_async(stream).await.unwrap();
+        let mut combined = false;
+        let mut subscriptions = BTreeSet::new();
+        while let Some(Ok(Message::Text(text))) = socket.next().await {
+            let request: Value = serde_json::from_str(&text).unwrap();
+            let id = request["id"].clone();
+            let names = || request["params"].as_array().unwrap().iter();
+            let result = match request["method"].as_str().unwrap() {
+                "SET_PROPERTY" => {
+                    combined = request["params"][1].as_bool().unwrap();
+                    Value::Null
+                }
+                "SUBSCRIBE" => {
+                    for name in names() {
+                        let name = name.as_str().unwrap();
+                        subscriptions.insert(name.to_string());
+                        let symbol = name.split('@').next().unwrap().to_uppercase();
+                        let data = json!({"e":"trade","E":1,"s":symbol,"t":1,"p":"1","q":"1","T":1,"m":true,"M":true});
+                        let event = match combined {
+                            true => json!({"stream": name, "data": data}),
+                            false => data,
+                        };
+                        socket.send(Message::text(event.to_string())).await.unwrap();
+                    }
+                    Value::Null
+                }
+                "UNSUBSCRIBE" => {
+                    for name in names() {
+                        subscriptions.remove(name.as_str().unwrap());
+                    }
+                    Value::Null
+                }
+                "LIST_SUBSCRIPTIONS" => json!(subscriptions),
+                _ => {
+                    let error = json!({"code": 2, "msg": "Invalid request", "id": id});
+                    socket.send(Message::text(error.to_string())).await.unwrap();
+                    continue;
+                }
+            };
+            let response = json!({"result": result, "id": id});
+            socket
+                .send(Message::text(response.to_string()))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn reconnect_delay() {
+        let cfg = StreamConfig::default();
+
+        assert_eq!(cfg.reconnect_delay(1), Duration::ZERO);
+        assert_eq!(cfg.reconnect_delay(2), Duration::from_secs(1));
+        assert_eq!(cfg.reconnect_delay(4), Duration::from_secs(4));
+        assert_eq!(cfg.reconnect_delay(8), Duration::from_secs(60));
+        assert_eq!(cfg.reconnect_delay(100), Duration::from_secs(60));
+    }
+
+    async fn control_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve_control(stream));
+            }
+        });
+        format!("ws://{addr}")
+    }
+
+    async fn next_message(market: &mut MarketStreams) -> StreamMessage {
+        market.next().await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn sharded_subscriptions() {
+        let client = StreamClient::new(StreamConfig {
+            base_url: control_server().await,
+            max_streams_per_connection: 2,
+            ..Default::default()
+        });
+        let trade = |symbol: &str| Stream::Trade {
+            symbol: symbol.to_string(),
+        };
+        let streams = [trade("BTCUSDT"), trade("ETHUSDT"), trade("BNBUSDT")];
+
+        let mut connection = client.connect(&[]).await.unwrap();
+        assert!(connection.subscribe(&streams).await.is_err());
+        connection.subscribe(&streams[..2]).await.unwrap();
+        assert_eq!(connection.capacity(), 0);
+        connection.close().await.unwrap();
+
+        let mut market = client.market_streams();
+        market.subscribe(&streams).await.unwrap();
+        market.subscribe(&streams[..1]).await.unwrap();
+        assert_eq!(market.connections(), 2);
+
+        let mut received = BTreeSet::new();
+        for _ in 0..streams.len() {
+            let Some(Ok(StreamMessage::Event(event))) = market.next().await else {
+                panic!("expected event");
+            };
+            received.insert(event.stream);
+        }
+        let expected: BTreeSet<String> = streams.iter().map(Stream::to_string).collect();
+        assert_eq!(received, expected);
+        assert_eq!(market.list_subscriptions().await.unwrap().len(), 3);
+
+        market.unsubscribe(&streams[2..]).await.unwrap();
+        assert_eq!(market.connections(), 1);
+        assert_eq!(
+            market.list_subscriptions().await.unwrap(),
+            streams[..2].to_vec()
+        );
+        market.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reconnect_silent_connection() {
+        let client = StreamClient::new(StreamConfig {
+            base_url: control_server().await,
+            silence_timeout: Duration::from_millis(200),
+            ..Default::default()
+        });
+        let stream = Stream::Trade {
+            symbol: String::from("BTCUSDT"),
+        };
+        let streams = vec![stream.to_string()];
+        let mut market = client.market_streams();
+        market.subscribe(&[stream]).await.unwrap();
+
+        assert!(matches!(
+            next_message(&mut market).await,
+            StreamMessage::Event(_)
+        ));
+        let StreamMessage::Reconnecting {
+            streams: lost,
+            attempt: 1,
+            reason,
+        } = next_message(&mut market).await
+        else {
+            panic!("expected reconnecting");
+        };
+        assert_eq!(lost, streams);
+        assert!(reason.starts_with("no message received"));
+        assert_eq!(
+            next_message(&mut market).await,
+            StreamMessage::Connected {
+                streams: streams.clone()
+            }
+        );
+        assert_eq!(
+            next_message(&mut market).await,
+            StreamMessage::Gap { streams }
+        );
+        assert!(matches!(
+            next_message(&mut market).await,
+            StreamMessage::Event(_)
+        ));
+        assert_eq!(market.connections(), 1);
+    }
+
+    /// Acknowledge subscriptions, then send a trade event every 50ms if subscribed to `btcusdt@trade`.
+    async fn serve_busy(stream: TcpStream) {
+        let mut socket = accept_async(stream).await.unwrap();
+        let mut busy = false;
+        let mut interval = tokio::time::interval(Duration::from_millis(50));
+        loop {
+            tokio::select! {
+                message = socket.next() => {
+                    let Some(Ok(Message::Text(text))) = message else {
+                        return;
+                    };
+                    let request: Value = serde_json::from_str(&text).unwrap();
+                    busy |= request["params"][0] == "btcusdt@trade";
+                    let response = json!({"result": null, "id": request["id"]});
+                    socket.send(Message::text(response.to_string())).await.unwrap();
+                }
+                _ = interval.tick(), if busy => {
+                    let data = json!({"e":"trade","E":1,"s":"BTCUSDT","t":1,"p":"1","q":"1","T":1,"m":true,"M":true});
+                    let event = json!({"stream": "btcusdt@trade", "data": data});
+                    if socket.send(Message::text(event.to_string())).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn reconnect_silent_shard_next_to_busy_one() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve_busy(stream));
+            }
+        });
+        let client = StreamClient::new(StreamConfig {
+            base_url: format!("ws://{addr}"),
+            max_streams_per_connection: 1,
+            silence_timeout: Duration::from_millis(300),
+            ..Default::default()
+        });
+        let trade = |symbol: &str| Stream::Trade {
+            symbol: symbol.to_string(),
+        };
+        let mut market = client.market_streams();
+        market
+            .subscribe(&[trade("BTCUSDT"), trade("ETHUSDT")])
+            .await
+            .unwrap();
+        assert_eq!(market.connections(), 2);
+
+        let reconnecting = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                if let StreamMessage::Reconnecting { streams, .. } = next_message(&mut market).await
+                {
+                    return streams;
+                }
+            }
+        });
+        assert_eq!(reconnecting.await.unwrap(), ["ethusdt@trade"]);
+    }
+
+    #[tokio::test]
+    async fn rotate_connection() {
+        let client = StreamClient::new(StreamConfig {
+            base_url: control_server().await,
+            rotate_after: Duration::from_millis(200),
+            request_timeout: Duration::from_millis(200),
+            ..Default::default()
+        });
+        let streams = [Stream::Trade {
+            symbol: String::from("BTCUSDT"),
+        }];
+        let mut market = client.market_streams();
+        market.subscribe(&streams).await.unwrap();
+
+        assert!(matches!(
+            next_message(&mut market).await,
+            StreamMessage::Event(_)
+        ));
+        assert_eq!(
+            next_message(&mut market).await,
+            StreamMessage::Connected {
+                streams: vec![streams[0].to_string()]
+            }
+        );
+        assert!(matches!(
+            next_message(&mut market).await,
+            StreamMessage::Event(_)
+        ));
+        assert_eq!(market.connections(), 1);
+        market.unsubscribe(&streams).await.unwrap();
+        assert!(market.next().await.is_none());
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::VecDeque;
+
+use futures_util::future::{Either, select, select_all};
+use tokio::time::Instant;
+
+use crate::spot::Error;
+
+use super::{Stream, StreamConfig, StreamConnection, StreamEvent};
+
+/// Event or connection lifecycle change of [`MarketStreams`].
+#[derive(Debug, PartialEq)]
+pub enum StreamMessage {
+    Event(Box<StreamEvent>),
+    /// A connection was opened and subscribed to the streams, after a reconnect or rotation.
+    Connected {
+        streams: Vec<String>,
+    },
+    /// A connection was lost, reconnect attempt `attempt` is scheduled.
+    Reconnecting {
+        streams: Vec<String>,
+        attempt: u32,
+        reason: String,
+    },
+    /// Events of the streams may have been missed while reconnecting.
+    Gap {
+        streams: Vec<String>,
+    },
+}
+
+/// Market data streams spread over as many connections as needed to stay within the
+/// per connection stream limit. Connections are opened on subscribe and closed once
+/// they have no streams left.
+///
+/// Lost or silent connections are reconnected and resubscribed. Connections are replaced
+/// after [`StreamConfig::rotate_after`], the old connection is closed once the new one is
+/// subscribed so events around a rotation may be received twice but none are missed.
+pub struct MarketStreams {
+    cfg: StreamConfig,
+    shards: Vec<Shard>,
+    reconnects: Vec<Reconnect>,
+    queue: VecDeque<Result<StreamMessage, Error>>,
+}
+
+struct Shard {
+    connection: StreamConnection,
+    rotate_at: Instant,
+}
+
+struct Reconnect {
+    streams: Vec<String>,
+    attempt: u32,
+    at: Instant,
+}
+
+impl MarketStreams {
+    pub(super) fn new(cfg: StreamConfig) -> Self {
+        Self {
+            cfg,
+            shards: Vec::new(),
+            reconnects: Vec::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Number of open connections.
+    pub fn connections(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Subscribe to streams, filling open connections before opening new ones.
+    pub async fn subscribe(&mut self, streams: &[Stream]) -> Result<(), Error> {
+        let mut remaining: Vec<String> = Vec::new();
+        for stream in streams {
+            let name = stream.to_string();
+            if !self.is_subscribed(&name) && !remaining.contains(&name) {
+                remaining.push(name);
+            }
+        }
+
+        for shard in &mut self.shards {
+            let count = shard.connection.capacity().min(remaining.len());
+            if count > 0 {
+                let batch = remaining.drain(..count).collect();
+                shard.connection.subscribe_names(batch).await?;
+            }
+        }
+        while !remaining.is_empty() {
+            let count = self.cfg.max_streams_per_connection.max(1);
+            let batch = remaining.drain(..count.min(remaining.len())).collect();
+            let shard = self.open(batch).await?;
+            self.shards.push(shard);
+        }
+        Ok(())
+    }
+
+    /// Unsubscribe from streams, closing connections left without streams.
+    pub async fn unsubscribe(&mut self, streams: &[Stream]) -> Result<(), Error> {
+        let names: Vec<String> = streams.iter().map(Stream::to_string).collect();
+        for shard in &mut self.shards {
+            let batch: Vec<Stream> = streams
+                .iter()
+                .zip(&names)
+                .filter(|(_, name)| shard.connection.subscriptions().contains(*name))
+                .map(|(stream, _)| stream.clone())
+                .collect();
+            shard.connection.unsubscribe(&batch).await?;
+        }
+        for reconnect in &mut self.reconnects {
+            reconnect.streams.retain(|name| !names.contains(name));
+        }
+        self.reconnects
+            .retain(|reconnect| !reconnect.streams.is_empty());
+
+        let (empty, shards): (Vec<_>, Vec<_>) = std::mem::take(&mut self.shards)
+            .into_iter()
+            .partition(|shard| shard.connection.subscriptions().is_empty());
+        self.shards = shards;
+        for shard in empty {
+            shard.connection.close().await?;
+        }
+        Ok(())
+    }
+
+    /// Streams the server reports as subscribed, over all open connections.
+    pub async fn list_subscriptions(&mut self) -> Result<Vec<Stream>, Error> {
+        let mut streams = Vec::new();
+        for shard in &mut self.shards {
+            streams.extend(shard.connection.list_subscriptions().await?);
+        }
+        Ok(streams)
+    }
+
+    /// Next event or lifecycle change, `None` once there are no subscriptions left.
+    pub async fn next(&mut self) -> Option<Result<StreamMessage, Error>> {
+        loop {
+            if let Some(message) = self.queue.pop_front() {
+                return Some(message);
+            }
+
+            let now = Instant::now();
+            if let Some(index) = self.shards.iter().position(|shard| shard.rotate_at <= now) {
+                self.rotate(index).await;
+                continue;
+            }
+            if let Some(index) = self
+                .reconnects
+                .iter()
+                .position(|reconnect| reconnect.at <= now)
+            {
+                let reconnect = self.reconnects.swap_remove(index);
+                self.reconnect(reconnect).await;
+                continue;
+            }
+            // Checked here as well as while reading: reads of all connections are restarted
+            // whenever one of them receives a message.
+            if let Some(index) = self
+                .shards
+                .iter()
+                .position(|shard| shard.connection.silent_at() <= now)
+            {
+                let reason = format!("no message received for {:?}", self.cfg.silence_timeout);
+                self.lost(index, reason);
+                continue;
+            }
+
+            let deadline = self
+                .shards
+                .iter()
+                .flat_map(|shard| [shard.rotate_at, shard.connection.silent_at()])
+                .chain(self.reconnects.iter().map(|reconnect| reconnect.at))
+                .min()?;
+            let sleep = Box::pin(tokio::time::sleep_until(deadline));
+            if self.shards.is_empty() {
+                sleep.await;
+                continue;
+            }
+
+            let next = self
+                .shards
+                .iter_mut()
+                .map(|shard| Box::pin(shard.connection.next()));
+            let received = match select(select_all(next), sleep).await {
+                Either::Left(((event, index, _), _)) => Some((event, index)),
+                Either::Right(_) => None,
+            };
+            if let Some((event, index)) = received {
+                self.receive(event, index);
+            }
+        }
+    }
+
+    pub async fn close(self) -> Result<(), Error> {
+        for shard in self.shards {
+            shard.connection.close().await?;
+        }
+        Ok(())
+    }
+
+    fn is_subscribed(&self, name: &str) -> bool {
+        let connected = self
+            .shards
+            .iter()
+            .any(|shard| shard.connection.subscriptions().contains(name));
+        connected
+            || self
+                .reconnects
+                .iter()
+                .any(|reconnect| reconnect.streams.iter().any(|stream| stream == name))
+    }
+
+    async fn open(&self, streams: Vec<String>) -> Result<Shard, Error> {
+        let mut connection = StreamConnection::connect(&self.cfg, &[]).await?;
+        connection.subscribe_names(streams).await?;
+        Ok(Shard {
+            rotate_at: connection.connected_at() + self.cfg.rotate_after,
+            connection,
+        })
+    }
+
+    fn receive(&mut self, event: Option<Result<StreamEvent, Error>>, index: usize) {
+        let event = match event {
+            Some(event) if self.shards[index].connection.is_alive() => event,
+            event => {
+                let reason = match event {
+                    Some(Err(error)) => error.to_string(),
+                    _ => String::from("connection closed"),
+                };
+                self.lost(index, reason);
+                return;
+            }
+        };
+        self.queue
+            .push_back(event.map(|event| StreamMessage::Event(Box::new(event))));
+    }
+
+    /// Drop a dead connection and schedule the reconnect of its streams.
+    fn lost(&mut self, index: usize, reason: String) {
+        let shard = self.shards.swap_remove(index);
+        self.schedule_reconnect(
+            shard.connection.subscriptions().iter().cloned().collect(),
+            1,
+            reason,
+        );
+    }
+
+    async fn reconnect(&mut self, reconnect: Reconnect) {
+        match self.open(reconnect.streams.clone()).await {
+            Ok(shard) => {
+                self.shards.push(shard);
+                let streams = reconnect.streams;
+                self.queue.push_back(Ok(StreamMessage::Connected {
+                    streams: streams.clone(),
+                }));
+                self.queue.push_back(Ok(StreamMessage::Gap { streams }));
+            }
+            Err(error) => {
+                self.schedule_reconnect(
+                    reconnect.streams,
+                    reconnect.attempt + 1,
+                    error.to_string(),
+                );
+            }
+        }
+    }
+
+    fn schedule_reconnect(&mut self, streams: Vec<String>, attempt: u32, reason: String) {
+        self.queue.push_back(Ok(StreamMessage::Reconnecting {
+            streams: streams.clone(),
+            attempt,
+            reason,
+        }));
+        self.reconnects.push(Reconnect {
+            streams,
+            attempt,
+            at: Instant::now() + self.cfg.reconnect_delay(attempt),
+        });
+    }
+
+    /// Replace a connection with a new subscribed one before closing it.
+    async fn rotate(&mut self, index: usize) {
+        let streams: Vec<String> = self.shards[index]
+            .connection
+            .subscriptions()
+            .iter()
+            .cloned()
+            .collect();
+        match self.open(streams.clone()).await {
+            Ok(shard) => {
+                let old = std::mem::replace(&mut self.shards[index], shard);
+                let events = old.connection.drain().await;
+                self.queue.extend(
+                    events
+                        .into_iter()
+                        .map(|event| event.map(|event| StreamMessage::Event(Box::new(event)))),
+                );
+                self.queue
+                    .push_back(Ok(StreamMessage::Connected { streams }));
+            }
+            Err(error) => {
+                // Keep the old connection and retry later.
+                self.shards[index].rotate_at = Instant::now() + self.cfg.reconnect_backoff;
+                self.queue.push_back(Err(error));
+            }
+        }
+    }
+}
@@ -0,0 +1,566 @@
+use std::{collections::VecDeque, time::Duration};
+
+use futures_util::future::{Either, select};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use tokio::time::Instant;
+
+use crate::spot::{
+    Client, ContingencyType, Error, ErrorCode, ExecutionType, ListenKeyParams,
+    OrderListOrderStatus, OrderListStatus, OrderSide, OrderStatus, OrderType, STPMode, TimeInForce,
+    Timestamp, serde::deserialize_str,
+};
+
+use super::{StreamConfig, StreamConnection};
+
+/// User data stream event.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "e")]
+pub enum UserDataEvent {
+    #[serde(rename = "executionReport")]
+    ExecutionReport(Box<ExecutionReportEvent>),
+    #[serde(rename = "outboundAccountPosition")]
+    AccountPosition(AccountPositionEvent),
+    #[serde(rename = "balanceUpdate")]
+    BalanceUpdate(BalanceUpdateEvent),
+    #[serde(rename = "listStatus")]
+    ListStatus(ListStatusEvent),
+    #[serde(rename = "listenKeyExpired")]
+    ListenKeyExpired(ListenKeyExpiredEvent),
+    #[serde(rename = "eventStreamTerminated")]
+    EventStreamTerminated(EventStreamTerminatedEvent),
+    /// Event not known to this version of the SDK.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Order update.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ExecutionReportEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+    #[serde(rename = "S")]
+    pub side: OrderSide,
+    #[serde(rename = "o")]
+    pub order_type: OrderType,
+    #[serde(rename = "f")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "q")]
+    pub qty: Decimal,
+    #[serde(rename = "p")]
+    pub price: Decimal,
+    #[serde(rename = "P")]
+    pub stop_price: Decimal,
+    #[serde(rename = "F")]
+    pub iceberg_qty: Decimal,
+    /// -1 if the order is not part of an order list.
+    #[serde(rename = "g")]
+    pub order_list_id: i64,
+    /// Client order id of the canceled order, empty otherwise.
+    #[serde(rename = "C")]
+    pub orig_client_order_id: String,
+    #[serde(rename = "x")]
+    pub execution_type: ExecutionType,
+    #[serde(rename = "X")]
+    pub status: OrderStatus,
+    /// `NONE` unless the order was rejected.
+    #[serde(rename = "r")]
+    pub reject_reason: String,
+    #[serde(rename = "i")]
+    pub order_id: i64,
+    #[serde(rename = "l")]
+    pub last_executed_qty: Decimal,
+    #[serde(rename = "z")]
+    pub cumulative_filled_qty: Decimal,
+    #[serde(rename = "L")]
+    pub last_executed_price: Decimal,
+    #[serde(rename = "n")]
+    pub commission: Decimal,
+    #[serde(rename = "N")]
+    pub commission_asset: Option<String>,
+    #[serde(rename = "T")]
+    pub transaction_time: Timestamp,
+    /// -1 unless the execution type is `TRADE`.
+    #[serde(rename = "t")]
+    pub trade_id: i64,
+    #[serde(rename = "v")]
+    pub prevented_match_id: Option<i64>,
+    #[serde(rename = "w")]
+    pub is_working: bool,
+    #[serde(rename = "m")]
+    pub is_maker: bool,
+    #[serde(rename = "O")]
+    pub order_creation_time: Timestamp,
+    #[serde(rename = "Z")]
+    pub cumulative_quote_qty: Decimal,
+    #[serde(rename = "Y")]
+    pub last_quote_qty: Decimal,
+    #[serde(rename = "Q")]
+    pub quote_order_qty: Decimal,
+    #[serde(rename = "W")]
+    pub working_time: Option<Timestamp>,
+    #[serde(rename = "V")]
+    pub self_trade_prevention_mode: STPMode,
+}
+
+/// Balances of the assets changed by an account update.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct AccountPositionEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+    #[serde(rename = "u")]
+    pub last_update_time: Timestamp,
+    #[serde(rename = "B")]
+    pub balances: Vec<AccountPositionBalance>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct AccountPositionBalance {
+    #[serde(rename = "a")]
+    pub asset: String,
+    #[serde(rename = "f")]
+    pub free: Decimal,
+    #[serde(rename = "l")]
+    pub locked: Decimal,
+}
+
+/// Deposit, withdrawal or transfer between accounts.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct BalanceUpdateEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+    #[serde(rename = "a")]
+    pub asset: String,
+    #[serde(rename = "d")]
+    pub delta: Decimal,
+    #[serde(rename = "T")]
+    pub clear_time: Timestamp,
+}
+
+/// Order list update, sent with an `executionReport` for each order of the list.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ListStatusEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "g")]
+    pub order_list_id: i64,
+    #[serde(rename = "c")]
+    pub contingency_type: ContingencyType,
+    #[serde(rename = "l")]
+    pub list_status_type: OrderListStatus,
+    #[serde(rename = "L")]
+    pub list_order_status: OrderListOrderStatus,
+    /// `NONE` unless the order list was rejected.
+    #[serde(rename = "r")]
+    pub reject_reason: String,
+    #[serde(rename = "C")]
+    pub list_client_order_id: String,
+    #[serde(rename = "T")]
+    pub transaction_time: Timestamp,
+    #[serde(rename = "O")]
+    pub orders: Vec<ListStatusOrder>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ListStatusOrder {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "i")]
+    pub order_id: i64,
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+}
+
+/// The listen key expired, no more events are sent on the connection.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ListenKeyExpiredEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+    #[serde(rename = "listenKey")]
+    pub listen_key: String,
+}
+
+/// The stream was terminated, no more events are sent on the connection.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct EventStreamTerminatedEvent {
+    #[serde(rename = "E")]
+    pub event_time: Timestamp,
+}
+
+/// Event or connection lifecycle change of a [`UserDataStream`].
+#[derive(Debug, PartialEq)]
+pub enum UserDataMessage {
+    Event(UserDataEvent),
+    /// Connected with a new listen key after the previous connection was lost or expired.
+    /// Events sent in between were missed, e.g. open orders and balances should be queried.
+    Connected,
+    /// The connection was lost or the listen key expired, reconnect attempt `attempt` is scheduled.
+    Reconnecting {
+        attempt: u32,
+        reason: String,
+    },
+}
+
+/// User data stream: creates the listen key, keeps it alive every 30 minutes and
+/// reconnects with a new listen key when the connection is lost or the key expires.
+///
+/// User data is not available on [`BASE_URL_STREAM_DATA1`](crate::spot::BASE_URL_STREAM_DATA1),
+/// connect to [`BASE_URL_STREAM`](crate::spot::BASE_URL_STREAM).
+///
+/// ```no_run
+/// # async fn run(client: binance::spot::Client) -> Result<(), binance::spot::Error> {
+/// use binance::spot::{BASE_URL_STREAM, stream::{StreamConfig, UserDataStream}};
+///
+/// let cfg = StreamConfig {
+///     base_url: BASE_URL_STREAM.to_string(),
+///     ..Default::default()
+/// };
+/// let mut stream = UserDataStream::connect(&client, cfg).await?;
+/// while let Some(message) = stream.next().await {
+///     println!("{:?}", message?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct UserDataStream<'a> {
+    client: &'a Client,
+    cfg: StreamConfig,
+    listen_key: String,
+    connection: Option<StreamConnection>,
+    keepalive_at: Instant,
+    attempt: u32,
+    reconnect_at: Instant,
+    queue: VecDeque<UserDataMessage>,
+}
+
+impl<'a> UserDataStream<'a> {
+    /// Binance recommends a keepalive every 30 minutes, listen keys expire after 60 minutes.
+    const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+    /// Delay before retrying a failed keepalive.
+    const KEEPALIVE_RETRY: Duration = Duration::from_secs(60);
+
+    pub async fn connect(client: &'a Client, cfg: StreamConfig) -> Result<Self, Error> {
+        let listen_key = client.start_user_data_stream().await?.result.listen_key;
+        let connection = StreamConnection::connect_raw(&cfg, &listen_key).await?;
+        let now = Instant::now();
+        Ok(Self {
+            client,
+            cfg,
+            listen_key,
+            connection: Some(connection),
+            keepalive_at: now + Self::KEEPALIVE_INTERVAL,
+            attempt: 0,
+            reconnect_at: now,
+            queue: VecDeque::new(),
+        })
+    }
+
+    pub fn listen_key(&self) -> &str {
+        &self.listen_key
+    }
+
+    /// Next event or lifecycle change. Keepalives and reconnects only happen while
+    /// this is polled, so it has to be polled regularly.
+    pub async fn next(&mut self) -> Option<Result<UserDataMessage, Error>> {
+        loop {
+            if let Some(message) = self.queue.pop_front() {
+                return Some(Ok(message));
+            }
+
+            let Some(connection) = self.connection.as_mut() else {
+                tokio::time::sleep_until(self.reconnect_at).await;
+                self.reconnect().await;
+                continue;
+            };
+
+            let keepalive = Box::pin(tokio::time::sleep_until(self.keepalive_at));
+            let received = match select(Box::pin(connection.read()), keepalive).await {
+                Either::Left((received, _)) => Some(received),
+                Either::Right(_) => None,
+            };
+            let alive = connection.is_alive();
+            match received {
+                Some(Some(Ok(text))) => match deserialize_str(&text) {
+                    Ok(UserDataEvent::ListenKeyExpired(event)) => {
+                        self.lost(String::from("listen key expired"));
+                        self.queue.push_front(UserDataMessage::Event(
+                            UserDataEvent::ListenKeyExpired(event),
+                        ));
+                    }
+                    Ok(UserDataEvent::Unknown) => {}
+                    Ok(event) => return Some(Ok(UserDataMessage::Event(event))),
+                    Err(error) => return Some(Err(error.into())),
+                },
+                Some(Some(Err(error))) if alive => return Some(Err(error)),
+                Some(Some(Err(error))) => self.lost(error.to_string()),
+                Some(None) => self.lost(String::from("connection closed")),
+                None => {
+                    if let Err(error) = self.keepalive().await {
+                        return Some(Err(error));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Close the connection and the listen key.
+    pub async fn close(self) -> Result<(), Error> {
+        if let Some(connection) = self.connection {
+            connection.close().await?;
+        }
+        let params = ListenKeyParams {
+            listen_key: self.listen_key,
+        };
+        self.client.close_user_data_stream(params).await?;
+        Ok(())
+    }
+
+    async fn keepalive(&mut self) -> Result<(), Error> {
+        let params = ListenKeyParams {
+            listen_key: self.listen_key.clone(),
+        };
+        match self.client.keepalive_user_data_stream(params).await {
+            Ok(_) => {
+                self.keepalive_at = Instant::now() + Self::KEEPALIVE_INTERVAL;
+                Ok(())
+            }
+            Err(error) if error.code() == Some(ErrorCode::InvalidListenKey) => {
+                self.lost(String::from("listen key expired"));
+                Ok(())
+            }
+            Err(error) => {
+                self.keepalive_at = Instant::now() + Self::KEEPALIVE_RETRY;
+                Err(error)
+            }
+        }
+    }
+
+    fn lost(&mut self, reason: String) {
+        self.connection = None;
+        self.attempt = 1;
+        self.reconnect_at = Instant::now();
+        self.queue.push_back(UserDataMessage::Reconnecting {
+            attempt: self.attempt,
+            reason,
+        });
+    }
+
+    async fn reconnect(&mut self) {
+        let connected = match self.client.start_user_data_stream().await {
+            Ok(response) => {
+                let listen_key = response.result.listen_key;
+                if listen_key != self.listen_key {
+                    let params = ListenKeyParams {
+                        listen_key: std::mem::replace(&mut self.listen_key, listen_key),
+                    };
+                    // The old key is most likely expired already.
+                    let _ = self.client.close_user_data_stream(params).await;
+                }
+                StreamConnection::connect_raw(&self.cfg, &self.listen_key).await
+            }
+            Err(error) => Err(error),
+        };
+        match connected {
+            Ok(connection) => {
+                self.connection = Some(connection);
+                self.keepalive_at = Instant::now() + Self::KEEPALIVE_INTERVAL;
+                self.queue.push_back(UserDataMessage::Connected);
+            }
+            Err(error) => {
+                self.attempt += 1;
+                self.reconnect_at = Instant::now() + self.cfg.reconnect_delay(self.attempt);
+                self.queue.push_back(UserDataMessage::Reconnecting {
+                    attempt: self.attempt,
+                    reason: error.to_string(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures_util::SinkExt;
+    use reqwest::Method;
+    use rust_decimal::dec;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::{accept_hdr_async, tungstenite::Message};
+
+    use super::*;
+    use crate::spot::{CannedTransport, ClientConfig, HttpResponse};
+
+    const EXECUTION_REPORT: &str = r#"{
+        "e": "executionReport",
+        "E": 1499405658658,
+        "s": "ETHBTC",
+        "c": "mUvoqJxFIILMdfAW5iGSOW",
+        "S": "BUY",
+        "o": "LIMIT",
+        "f": "GTC",
+        "q": "1.00000000",
+        "p": "0.10264410",
+        "P": "0.00000000",
+        "F": "0.00000000",
+        "g": -1,
+        "C": "",
+        "x": "TRADE",
+        "X": "PARTIALLY_FILLED",
+        "r": "NONE",
+        "i": 4293153,
+        "l": "0.50000000",
+        "z": "0.50000000",
+        "L": "0.10264410",
+        "n": "0.00050000",
+        "N": "ETH",
+        "T": 1499405658657,
+        "t": 7,
+        "v": 3,
+        "I": 8641984,
+        "w": true,
+        "m": false,
+        "M": false,
+        "O": 1499405658657,
+        "Z": "0.05132205",
+        "Y": "0.05132205",
+        "Q": "0.00000000",
+        "W": 1499405658657,
+        "V": "NONE"
+    }"#;
+
+    #[test]
+    fn deserialize_user_data_events() {
+        let UserDataEvent::ExecutionReport(report) = deserialize_str(EXECUTION_REPORT).unwrap()
+        else {
+            panic!("expected execution report");
+        };
+        assert_eq!(report.execution_type, ExecutionType::Trade);
+        assert_eq!(report.status, OrderStatus::PartiallyFilled);
+        assert_eq!(report.last_executed_qty, dec!(0.5));
+        assert_eq!(report.commission_asset.as_deref(), Some("ETH"));
+
+        let json = r#"{
+            "e": "listStatus",
+            "E": 1564035303637,
+            "s": "ETHBTC",
+            "g": 2,
+            "c": "OCO",
+            "l": "EXEC_STARTED",
+            "L": "EXECUTING",
+            "r": "NONE",
+            "C": "F4QN4G8DlFATFlIUQ0cjdD",
+            "T": 1564035303625,
+            "O": [
+                {"s": "ETHBTC", "i": 17, "c": "AJYsMjErWJesZvqlJCTUgL"},
+                {"s": "ETHBTC", "i": 18, "c": "bfYPSQdLoqAJeNrOr9adzq"}
+            ]
+        }"#;
+        let UserDataEvent::ListStatus(list) = deserialize_str(json).unwrap() else {
+            panic!("expected list status");
+        };
+        assert_eq!(list.list_status_type, OrderListStatus::ExecStarted);
+        assert_eq!(list.orders.len(), 2);
+
+        let json = r#"{"e":"balanceUpdate","E":1573200697110,"a":"BTC","d":"100.00000000","T":1573200697068}"#;
+        let expected = UserDataEvent::BalanceUpdate(BalanceUpdateEvent {
+            event_time: 1573200697110,
+            asset: String::from("BTC"),
+            delta: dec!(100),
+            clear_time: 1573200697068,
+        });
+        assert_eq!(expected, deserialize_str(json).unwrap());
+
+        let json = r#"{"e":"externalLockUpdate","E":1581557507324,"a":"NEO","d":"10.00000000","T":1581557507268}"#;
+        assert_eq!(UserDataEvent::Unknown, deserialize_str(json).unwrap());
+    }
+
+    #[allow(clippy::result_large_err)]
+    #[tokio::test]
+    async fn reconnect_on_expired_listen_key() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let mut paths = Vec::new();
+            let unknown = r#"{"e":"externalLockUpdate","E":1,"a":"NEO","d":"1","T":1}"#;
+            for messages in [
+                &[r#"{"e":"listenKeyExpired","E":1,"listenKey":"key-1"}"#][..],
+                &[unknown, EXECUTION_REPORT],
+            ] {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut path = String::new();
+                let callback =
+                    |request: &tokio_tungstenite::tungstenite::handshake::server::Request,
+                     response| {
+                        path = request.uri().to_string();
+                        Ok(response)
+                    };
+                let mut socket = accept_hdr_async(stream, callback).await.unwrap();
+                for message in messages {
+                    socket.send(Message::text(*message)).await.unwrap();
+                }
+                paths.push(path);
+                if paths.len() == 2 {
+                    socket.close(None).await.unwrap();
+                }
+            }
+            paths
+        });
+
+        let transport = Arc::new(CannedTransport::new());
+        for key in ["key-1", "key-2"] {
+            let body = format!(r#"{{"listenKey":"{key}"}}"#);
+            transport.push(
+                Method::POST,
+                "/api/v3/userDataStream",
+                HttpResponse::json(200, body),
+            );
+        }
+        let client = Client::new(ClientConfig {
+            api_key: Some("api-key".into()),
+            transport: Some(transport.clone()),
+            ..Default::default()
+        })
+        .unwrap();
+        let cfg = StreamConfig {
+            base_url: format!("ws://{addr}"),
+            ..Default::default()
+        };
+
+        let mut stream = UserDataStream::connect(&client, cfg).await.unwrap();
+        let mut next = async || stream.next().await.unwrap().unwrap();
+
+        assert!(matches!(
+            next().await,
+            UserDataMessage::Event(UserDataEvent::ListenKeyExpired(_))
+        ));
+        assert_eq!(
+            next().await,
+            UserDataMessage::Reconnecting {
+                attempt: 1,
+                reason: String::from("listen key expired"),
+            }
+        );
+        assert_eq!(next().await, UserDataMessage::Connected);
+        // The unknown event is skipped.
+        assert!(matches!(
+            next().await,
+            UserDataMessage::Event(UserDataEvent::ExecutionReport(_))
+        ));
+        assert_eq!(stream.listen_key(), "key-2");
+        assert_eq!(server.await.unwrap(), ["/ws/key-1", "/ws/key-2"]);
+
+        // The expired key is closed once replaced.
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].headers["X-MBX-APIKEY"], "api-key");
+        assert_eq!(requests[2].method, Method::DELETE);
+        assert!(requests[2].url.ends_with("?listenKey=key-1"));
+    }
+}
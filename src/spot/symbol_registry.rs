@@ -0,0 +1,365 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, PoisonError, RwLock},
+    time::Duration,
+};
+
+use tokio::{sync::broadcast, time::Instant};
+
+use crate::spot::{
+    Error, ErrorCode, ExchangeFilter, ExchangeInfo, GetExchangeInfoParams, SpotApi, SymbolInfo,
+    SymbolStatus,
+};
+
+/// Trading status of a symbol changed between two refreshes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolStatusChange {
+    pub symbol: String,
+    /// `None` for a newly listed symbol.
+    pub previous: Option<SymbolStatus>,
+    /// `None` for a removed symbol.
+    pub status: Option<SymbolStatus>,
+}
+
+#[derive(Default)]
+struct Index {
+    symbols: BTreeMap<String, Arc<SymbolInfo>>,
+    by_base_asset: BTreeMap<String, Vec<Arc<SymbolInfo>>>,
+    by_quote_asset: BTreeMap<String, Vec<Arc<SymbolInfo>>>,
+    exchange_filters: Vec<ExchangeFilter>,
+}
+
+impl Index {
+    fn new(info: ExchangeInfo) -> Self {
+        let mut index = Index {
+            exchange_filters: info.exchange_filters,
+            ..Default::default()
+        };
+        for symbol in info.symbols.into_iter().map(Arc::new) {
+            index
+                .by_base_asset
+                .entry(symbol.base_asset.clone())
+                .or_default()
+                .push(Arc::clone(&symbol));
+            index
+                .by_quote_asset
+                .entry(symbol.quote_asset.clone())
+                .or_default()
+                .push(Arc::clone(&symbol));
+            index.symbols.insert(symbol.symbol.clone(), symbol);
+        }
+        index
+    }
+
+    fn changes(&self, next: &Index) -> Vec<SymbolStatusChange> {
+        let status = |index: &Index, symbol: &str| index.symbols.get(symbol).map(|s| s.status);
+        let mut symbols: Vec<&String> = self.symbols.keys().chain(next.symbols.keys()).collect();
+        symbols.sort();
+        symbols.dedup();
+
+        symbols
+            .into_iter()
+            .filter_map(|symbol| {
+                let previous = status(self, symbol);
+                let status = status(next, symbol);
+                (previous != status).then(|| SymbolStatusChange {
+                    symbol: symbol.clone(),
+                    previous,
+                    status,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Exchange info loaded once and indexed by symbol and asset.
+///
+/// ```no_run
+/// # async fn run(client: binance::spot::Client) -> Result<(), binance::spot::Error> {
+/// use std::time::Duration;
+/// use binance::spot::SymbolRegistry;
+///
+/// let registry = SymbolRegistry::load(&client, Duration::from_secs(3600)).await?;
+/// let mut changes = registry.subscribe();
+/// if let Some(info) = registry.symbol("BTCUSDT") {
+///     println!("{:?}", info.status);
+/// }
+/// tokio::select! {
+///     error = registry.auto_refresh(&client) => return Err(error),
+///     Ok(change) = changes.recv() => println!("{change:?}"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct SymbolRegistry {
+    index: RwLock<Index>,
+    refresh_interval: Duration,
+    refreshed_at: RwLock<Instant>,
+    changes: broadcast::Sender<SymbolStatusChange>,
+}
+
+impl SymbolRegistry {
+    const CHANNEL_CAPACITY: usize = 1024;
+    /// Minimum time between refreshes triggered by [`refresh_on_error`](Self::refresh_on_error),
+    /// exchange info weighs 20.
+    pub const MIN_ERROR_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+    /// Load exchange info of all symbols, stale after `refresh_interval`.
+    pub async fn load(api: &impl SpotApi, refresh_interval: Duration) -> Result<Self, Error> {
+        let info = api.get_exchange_info(params()).await?;
+        let (changes, _) = broadcast::channel(Self::CHANNEL_CAPACITY);
+        Ok(Self {
+            index: RwLock::new(Index::new(info)),
+            refresh_interval,
+            refreshed_at: RwLock::new(Instant::now()),
+            changes,
+        })
+    }
+
+    pub fn symbol(&self, symbol: &str) -> Option<Arc<SymbolInfo>> {
+        self.index().symbols.get(symbol).cloned()
+    }
+
+    /// Symbols trading `asset` as the base asset, e.g. BTCUSDT for BTC.
+    pub fn by_base_asset(&self, asset: &str) -> Vec<Arc<SymbolInfo>> {
+        self.index()
+            .by_base_asset
+            .get(asset)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Symbols quoted in `asset`, e.g. BTCUSDT for USDT.
+    pub fn by_quote_asset(&self, asset: &str) -> Vec<Arc<SymbolInfo>> {
+        self.index()
+            .by_quote_asset
+            .get(asset)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn symbols(&self) -> Vec<Arc<SymbolInfo>> {
+        self.index().symbols.values().cloned().collect()
+    }
+
+    pub fn exchange_filters(&self) -> Vec<ExchangeFilter> {
+        self.index().exchange_filters.clone()
+    }
+
+    /// Receive symbol status changes found by later refreshes.
+    pub fn subscribe(&self) -> broadcast::Receiver<SymbolStatusChange> {
+        self.changes.subscribe()
+    }
+
+    /// Whether `refresh_interval` passed since the last refresh.
+    pub fn is_stale(&self) -> bool {
+        self.refreshed_at().elapsed() >= self.refresh_interval
+    }
+
+    /// Reload exchange info, notify subscribers and return the status changes.
+    pub async fn refresh(&self, api: &impl SpotApi) -> Result<Vec<SymbolStatusChange>, Error> {
+        let info = api.get_exchange_info(params()).await?;
+        let next = Index::new(info);
+
+        let changes = {
+            let mut index = self.index.write().unwrap_or_else(PoisonError::into_inner);
+            let changes = index.changes(&next);
+            *index = next;
+            changes
+        };
+        *self
+            .refreshed_at
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Instant::now();
+        for change in &changes {
+            // No subscribers is not an error.
+            let _ = self.changes.send(change.clone());
+        }
+        Ok(changes)
+    }
+
+    /// Refresh if the exchange info is stale, see [`is_stale`](Self::is_stale).
+    pub async fn refresh_if_stale(&self, api: &impl SpotApi) -> Result<bool, Error> {
+        if !self.is_stale() {
+            return Ok(false);
+        }
+        self.refresh(api).await?;
+        Ok(true)
+    }
+
+    /// Refresh if the order error may be caused by outdated exchange info:
+    /// an unknown symbol (-1121) or a filter failure (-1013). Skipped within
+    /// [`MIN_ERROR_REFRESH_INTERVAL`](Self::MIN_ERROR_REFRESH_INTERVAL) of the last refresh,
+    /// so repeated rejections do not use up the request weight.
+    pub async fn refresh_on_error(&self, api: &impl SpotApi, error: &Error) -> Result<bool, Error> {
+        let outdated = match error {
+            Error::Api { code, msg, .. } => {
+                *code == ErrorCode::BadSymbol
+                    || (*code == ErrorCode::InvalidMessage && msg.starts_with("Filter failure"))
+            }
+            _ => false,
+        };
+        if !outdated || self.refreshed_at().elapsed() < Self::MIN_ERROR_REFRESH_INTERVAL {
+            return Ok(false);
+        }
+        self.refresh(api).await?;
+        Ok(true)
+    }
+
+    /// Refresh every `refresh_interval`.
+    ///
+    /// Stops at the first failed refresh and returns its error. The cached exchange info stays
+    /// usable, call `auto_refresh` again to resume, e.g. after a delay for transient errors.
+    pub async fn auto_refresh(&self, api: &impl SpotApi) -> Error {
+        loop {
+            let refresh_at = self.refreshed_at() + self.refresh_interval;
+            tokio::time::sleep_until(refresh_at).await;
+            if let Err(error) = self.refresh(api).await {
+                return error;
+            }
+        }
+    }
+
+    fn index(&self) -> std::sync::RwLockReadGuard<'_, Index> {
+        self.index.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn refreshed_at(&self) -> Instant {
+        *self
+            .refreshed_at
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn params() -> GetExchangeInfoParams {
+    GetExchangeInfoParams {
+        symbol: None,
+        symbols: None,
+        permissions: None,
+        show_permission_sets: None,
+        symbol_status: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Method;
+
+    use super::*;
+    use crate::spot::{CannedTransport, Client, ClientConfig, HttpResponse};
+
+    fn symbol(symbol: &str, base: &str, quote: &str, status: &str) -> String {
+        format!(
+            r#"{{"symbol":"{symbol}","status":"{status}","baseAsset":"{base}","baseAssetPrecision":8,
+            "quoteAsset":"{quote}","quotePrecision":8,"quoteAssetPrecision":8,"baseCommissionPrecision":8,
+            "quoteCommissionPrecision":8,"orderTypes":["LIMIT"],"icebergAllowed":true,"ocoAllowed":true,
+            "otoAllowed":true,"quoteOrderQtyMarketAllowed":true,"allowTrailingStop":false,
+            "cancelReplaceAllowed":false,"amendAllowed":false,"isSpotTradingAllowed":true,
+            "isMarginTradingAllowed":false,"filters":[],"permissions":[],"permissionSets":[["SPOT"]],
+            "defaultSelfTradePreventionMode":"NONE","allowedSelfTradePreventionModes":["NONE"]}}"#
+        )
+    }
+
+    fn exchange_info(symbols: &[String]) -> HttpResponse {
+        let json = format!(
+            r#"{{"timezone":"UTC","serverTime":1565246363776,"rateLimits":[],
+            "exchangeFilters":[{{"filterType":"EXCHANGE_MAX_NUM_ORDERS","maxNumOrders":1000}}],
+            "symbols":[{}]}}"#,
+            symbols.join(",")
+        );
+        HttpResponse::json(200, &json)
+    }
+
+    #[tokio::test]
+    async fn index_and_refresh() {
+        let transport = Arc::new(CannedTransport::new());
+        let path = "/api/v3/exchangeInfo";
+        transport.push(
+            Method::GET,
+            path,
+            exchange_info(&[
+                symbol("BTCUSDT", "BTC", "USDT", "TRADING"),
+                symbol("ETHBTC", "ETH", "BTC", "TRADING"),
+                symbol("ETHUSDT", "ETH", "USDT", "TRADING"),
+            ]),
+        );
+        transport.push(
+            Method::GET,
+            path,
+            exchange_info(&[
+                symbol("BTCUSDT", "BTC", "USDT", "HALT"),
+                symbol("ETHUSDT", "ETH", "USDT", "TRADING"),
+                symbol("BNBUSDT", "BNB", "USDT", "BREAK"),
+            ]),
+        );
+        let cfg = ClientConfig {
+            transport: Some(transport.clone()),
+            ..Default::default()
+        };
+        let client = Client::new(cfg).unwrap();
+
+        let registry = SymbolRegistry::load(&client, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        let mut changes = registry.subscribe();
+        assert!(!registry.is_stale());
+        assert_eq!(registry.symbols().len(), 3);
+        assert_eq!(registry.exchange_filters().len(), 1);
+        assert_eq!(
+            registry.symbol("ETHBTC").unwrap().status,
+            SymbolStatus::Trading
+        );
+        let names = |symbols: Vec<Arc<SymbolInfo>>| {
+            symbols
+                .iter()
+                .map(|symbol| symbol.symbol.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(registry.by_base_asset("ETH")), ["ETHBTC", "ETHUSDT"]);
+        assert_eq!(registry.by_quote_asset("BTC").len(), 1);
+
+        // Only order errors caused by outdated exchange info refresh.
+        let error = Error::Api {
+            status: 400,
+            code: ErrorCode::InvalidMessage,
+            msg: String::from("Mandatory parameter 'side' was not sent."),
+        };
+        assert!(!registry.refresh_on_error(&client, &error).await.unwrap());
+        let error = Error::Api {
+            status: 400,
+            code: ErrorCode::BadSymbol,
+            msg: String::from("Invalid symbol."),
+        };
+        // Too soon after loading.
+        assert!(!registry.refresh_on_error(&client, &error).await.unwrap());
+        *registry.refreshed_at.write().unwrap() -= SymbolRegistry::MIN_ERROR_REFRESH_INTERVAL;
+        assert!(registry.refresh_on_error(&client, &error).await.unwrap());
+        assert!(!registry.refresh_on_error(&client, &error).await.unwrap());
+
+        let expected = [
+            SymbolStatusChange {
+                symbol: String::from("BNBUSDT"),
+                previous: None,
+                status: Some(SymbolStatus::Break),
+            },
+            SymbolStatusChange {
+                symbol: String::from("BTCUSDT"),
+                previous: Some(SymbolStatus::Trading),
+                status: Some(SymbolStatus::Halt),
+            },
+            SymbolStatusChange {
+                symbol: String::from("ETHBTC"),
+                previous: Some(SymbolStatus::Trading),
+                status: None,
+            },
+        ];
+        for change in expected {
+            assert_eq!(changes.recv().await.unwrap(), change);
+        }
+        assert!(changes.try_recv().is_err());
+        assert!(registry.symbol("ETHBTC").is_none());
+        assert_eq!(names(registry.by_base_asset("ETH")), ["ETHUSDT"]);
+        assert_eq!(transport.requests().len(), 2);
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{Mutex, PoisonError},
+};
+
+use reqwest::{Method, header::HeaderMap};
+
+use super::Error;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// HTTP request built and signed by the client.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    /// Full URL with query string.
+    pub url: String,
+    pub headers: HeaderMap,
+    /// Form encoded body of POST and PUT requests.
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Response with JSON body and no headers.
+    pub fn json(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+}
+
+/// Sends HTTP requests for [`Client`](super::Client).
+pub trait HttpTransport: Send + Sync {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, Error>>;
+}
+
+/// Default transport over a pooled `reqwest::Client`.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, Error>> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(request.method, request.url)
+                .headers(request.headers);
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+
+            let response = builder.send().await?;
+            let status = response.status().as_u16();
+            let headers = response.headers().clone();
+            let body = response.text().await?;
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+/// In-memory transport serving canned responses, for tests without network.
+///
+/// Responses are queued per method and URL path and served in order.
+/// A request without a queued response fails with [`Error::Msg`].
+#[derive(Debug, Default)]
+pub struct CannedTransport {
+    responses: Mutex<BTreeMap<(String, String), VecDeque<HttpResponse>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl CannedTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue response for requests to `path`, e.g. `/api/v3/time`.
+    pub fn push(&self, method: Method, path: &str, response: HttpResponse) {
+        self.responses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry((method.to_string(), path.to_string()))
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Requests received so far.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl HttpTransport for CannedTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, Error>> {
+        let path = request
+            .url
+            .parse::<reqwest::Url>()
+            .map(|url| url.path().to_string())
+            .unwrap_or_default();
+        let key = (request.method.to_string(), path);
+
+        let response = self
+            .responses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(&key)
+            .and_then(VecDeque::pop_front);
+        self.requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(request);
+
+        Box::pin(async move {
+            response
+                .ok_or_else(|| Error::Msg(format!("no canned response for {} {}", key.0, key.1)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: Method, url: &str) -> HttpRequest {
+        HttpRequest {
+            method,
+            url: url.to_string(),
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn canned_transport() {
+        let transport = CannedTransport::new();
+        transport.push(Method::GET, "/api/v3/ping", HttpResponse::json(200, "{}"));
+
+        let response = transport
+            .send(request(
+                Method::GET,
+                "https://api.binance.com/api/v3/ping?a=1",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "{}");
+
+        let error = transport
+            .send(request(Method::GET, "https://api.binance.com/api/v3/ping"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "no canned response for GET /api/v3/ping");
+        assert_eq!(transport.requests().len(), 2);
+    }
+}
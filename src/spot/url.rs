@@ -7,9 +7,16 @@ pub const BASE_URL_API2: &str = "https://api2.binance.com";
 pub const BASE_URL_API3: &str = "https://api3.binance.com";
 pub const BASE_URL_API4: &str = "https://api4.binance.com";
 
+/// For market data and user data streams.
+pub const BASE_URL_STREAM: &str = "wss://stream.binance.com:9443";
+/// For market data streams only.
 pub const BASE_URL_STREAM_DATA1: &str = "wss://data-stream.binance.vision:9443";
 pub const BASE_URL_STREAM_DATA2: &str = "wss://data-stream.binance.vision:443";
 
+pub const BASE_URL_WS_API: &str = "wss://ws-api.binance.com:443/ws-api/v3";
+pub const BASE_URL_WS_API_9443: &str = "wss://ws-api.binance.com:9443/ws-api/v3";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Path {
     // General endpoints.
     Ping,
@@ -40,10 +47,18 @@ pub enum Path {
     OrderListOTO,
     OrderListOTOCO,
     OrderList,
+    OpenOrderList,
     SOROrder,
     SOROrderTest,
 
     RateLimitOrder,
+
+    // Account endpoints
+    Account,
+    MyAllocations,
+
+    // User data stream endpoints
+    UserDataStream,
 }
 
 impl std::fmt::Display for Path {
@@ -78,16 +93,29 @@ impl std::fmt::Display for Path {
             Self::OrderListOTO => "/api/v3/orderList/oto",
             Self::OrderListOTOCO => "/api/v3/orderList/otoco",
             Self::OrderList => "/api/v3/orderList",
+            Self::OpenOrderList => "/api/v3/openOrderList",
             Self::SOROrder => "/api/v3/sor/order",
             Self::SOROrderTest => "/api/v3/sor/order/test",
 
             Self::RateLimitOrder => "/api/v3/rateLimit/order",
+
+            // Account endpoints
+            Self::Account => "/api/v3/account",
+            Self::MyAllocations => "/api/v3/myAllocations",
+
+            // User data stream endpoints
+            Self::UserDataStream => "/api/v3/userDataStream",
         };
 
         write!(f, "{}", s)
     }
 }
 
-// TODO: X-MBX-USED-WEIGHT-(intervalNum)(intervalLetter)
+/// X-MBX-USED-WEIGHT-(intervalNum)(intervalLetter), e.g. X-MBX-USED-WEIGHT-1M
+pub const HEADER_X_MBX_USED_WEIGHT_PREFIX: &str = "X-MBX-USED-WEIGHT-";
+/// X-MBX-ORDER-COUNT-(intervalNum)(intervalLetter), e.g. X-MBX-ORDER-COUNT-10S
+pub const HEADER_X_MBX_ORDER_COUNT_PREFIX: &str = "X-MBX-ORDER-COUNT-";
 pub const HEADER_RETRY_AFTER: &str = "Retry-After";
 pub const HEADER_X_MBX_APIKEY: &str = "X-MBX-APIKEY";
+
+pub const CONTENT_TYPE_FORM: &str = "application/x-www-form-urlencoded";
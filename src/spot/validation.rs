@@ -0,0 +1,707 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::spot::{Error, ErrorCode, Filter, NewOrderParams, OrderSide, OrderType, SymbolInfo};
+
+/// Rule of a symbol filter an order breaks, rejected by Binance with `-1013 Filter failure`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterViolation {
+    /// Price or stop price outside `[min_price, max_price]`.
+    PriceOutOfRange {
+        price: Decimal,
+        min_price: Decimal,
+        max_price: Decimal,
+    },
+    /// Price or stop price not a multiple of the tick size.
+    PriceTickSize { price: Decimal, tick_size: Decimal },
+    /// Price outside the band around the reference price.
+    PercentPrice {
+        price: Decimal,
+        min_price: Decimal,
+        max_price: Decimal,
+    },
+    /// Price outside the band around the reference price for the order side.
+    PercentPriceBySide {
+        side: OrderSide,
+        price: Decimal,
+        min_price: Decimal,
+        max_price: Decimal,
+    },
+    /// Quantity outside `[min_qty, max_qty]`.
+    LotSize {
+        qty: Decimal,
+        min_qty: Decimal,
+        max_qty: Decimal,
+    },
+    /// Quantity not a multiple of the step size.
+    LotStepSize { qty: Decimal, step_size: Decimal },
+    /// MARKET order quantity outside `[min_qty, max_qty]`.
+    MarketLotSize {
+        qty: Decimal,
+        min_qty: Decimal,
+        max_qty: Decimal,
+    },
+    /// MARKET order quantity not a multiple of the step size.
+    MarketLotStepSize { qty: Decimal, step_size: Decimal },
+    /// Notional value below the minimum.
+    MinNotional {
+        notional: Decimal,
+        min_notional: Decimal,
+    },
+    /// Notional value outside `[min_notional, max_notional]`.
+    Notional {
+        notional: Decimal,
+        min_notional: Decimal,
+        max_notional: Decimal,
+    },
+    /// Iceberg order split into too many parts.
+    IcebergParts { parts: Decimal, limit: u64 },
+    /// Trailing delta outside the range for the order type and side.
+    TrailingDelta { delta: u64, min: u64, max: u64 },
+}
+
+impl FilterViolation {
+    /// Name of the filter, as in the `Filter failure: <name>` message.
+    pub fn filter(&self) -> &'static str {
+        match self {
+            Self::PriceOutOfRange { .. } | Self::PriceTickSize { .. } => "PRICE_FILTER",
+            Self::PercentPrice { .. } => "PERCENT_PRICE",
+            Self::PercentPriceBySide { .. } => "PERCENT_PRICE_BY_SIDE",
+            Self::LotSize { .. } | Self::LotStepSize { .. } => "LOT_SIZE",
+            Self::MarketLotSize { .. } | Self::MarketLotStepSize { .. } => "MARKET_LOT_SIZE",
+            Self::MinNotional { .. } => "MIN_NOTIONAL",
+            Self::Notional { .. } => "NOTIONAL",
+            Self::IcebergParts { .. } => "ICEBERG_PARTS",
+            Self::TrailingDelta { .. } => "TRAILING_DELTA",
+        }
+    }
+
+    /// Error code Binance rejects the order with.
+    pub fn code(&self) -> ErrorCode {
+        ErrorCode::InvalidMessage
+    }
+}
+
+impl std::fmt::Display for FilterViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Filter failure: {}: ", self.filter())?;
+        match self {
+            Self::PriceOutOfRange {
+                price,
+                min_price,
+                max_price,
+            }
+            | Self::PercentPrice {
+                price,
+                min_price,
+                max_price,
+            }
+            | Self::PercentPriceBySide {
+                price,
+                min_price,
+                max_price,
+                ..
+            } => write!(f, "price {price} outside [{min_price}, {max_price}]"),
+            Self::PriceTickSize { price, tick_size } => {
+                write!(f, "price {price} is not a multiple of {tick_size}")
+            }
+            Self::LotSize {
+                qty,
+                min_qty,
+                max_qty,
+            }
+            | Self::MarketLotSize {
+                qty,
+                min_qty,
+                max_qty,
+            } => write!(f, "quantity {qty} outside [{min_qty}, {max_qty}]"),
+            Self::LotStepSize { qty, step_size } | Self::MarketLotStepSize { qty, step_size } => {
+                write!(f, "quantity {qty} is not a multiple of {step_size}")
+            }
+            Self::MinNotional {
+                notional,
+                min_notional,
+            } => write!(f, "notional {notional} below {min_notional}"),
+            Self::Notional {
+                notional,
+                min_notional,
+                max_notional,
+            } => write!(
+                f,
+                "notional {notional} outside [{min_notional}, {max_notional}]"
+            ),
+            Self::IcebergParts { parts, limit } => {
+                write!(f, "{parts} iceberg parts, limit {limit}")
+            }
+            Self::TrailingDelta { delta, min, max } => {
+                write!(f, "trailing delta {delta} outside [{min}, {max}]")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FilterViolation {}
+
+/// The error Binance would answer the order with, so local and remote filter failures
+/// can be handled alike.
+impl From<FilterViolation> for Error {
+    fn from(violation: FilterViolation) -> Self {
+        Error::Api {
+            status: 400,
+            code: violation.code(),
+            msg: violation.to_string(),
+        }
+    }
+}
+
+/// Direction to round a price or quantity to a valid value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+    Nearest,
+}
+
+impl SymbolInfo {
+    /// Check the order against the symbol filters, see [`validate_order`].
+    pub fn validate_order(
+        &self,
+        order: &NewOrderParams,
+        reference_price: Option<Decimal>,
+    ) -> Result<(), Vec<FilterViolation>> {
+        validate_order(&self.filters, order, reference_price)
+    }
+
+    /// Round price to the PRICE_FILTER tick size, within `[min_price, max_price]`.
+    pub fn round_price(&self, price: Decimal, rounding: Rounding) -> Decimal {
+        round_price(&self.filters, price, rounding)
+    }
+
+    /// Round quantity of a non MARKET order, see [`round_order_qty`](Self::round_order_qty).
+    pub fn round_qty(&self, qty: Decimal, rounding: Rounding) -> Decimal {
+        round_qty(&self.filters, qty, OrderType::Limit, rounding)
+    }
+
+    /// Round quantity to the LOT_SIZE step size within `[min_qty, max_qty]`, and for MARKET
+    /// orders to MARKET_LOT_SIZE as well.
+    pub fn round_order_qty(
+        &self,
+        qty: Decimal,
+        order_type: OrderType,
+        rounding: Rounding,
+    ) -> Decimal {
+        round_qty(&self.filters, qty, order_type, rounding)
+    }
+}
+
+fn round_price(filters: &[Filter], price: Decimal, rounding: Rounding) -> Decimal {
+    filters
+        .iter()
+        .find_map(|filter| match *filter {
+            Filter::PriceFilter {
+                min_price,
+                max_price,
+                tick_size,
+            } => Some(round_in_range(
+                price, min_price, max_price, tick_size, rounding,
+            )),
+            _ => None,
+        })
+        .unwrap_or(price)
+}
+
+fn round_qty(
+    filters: &[Filter],
+    qty: Decimal,
+    order_type: OrderType,
+    rounding: Rounding,
+) -> Decimal {
+    filters.iter().fold(qty, |qty, filter| match *filter {
+        Filter::LotSize {
+            min_qty,
+            max_qty,
+            step_size,
+        } => round_in_range(qty, min_qty, max_qty, step_size, rounding),
+        Filter::MarketLotSize {
+            min_qty,
+            max_qty,
+            step_size,
+        } if order_type == OrderType::Market => {
+            round_in_range(qty, min_qty, max_qty, step_size, rounding)
+        }
+        _ => qty,
+    })
+}
+
+/// Round value to the grid starting at `min`, then clamp it into `[min, max]`.
+/// A zero `max` is no upper bound.
+fn round_in_range(
+    value: Decimal,
+    min: Decimal,
+    max: Decimal,
+    step: Decimal,
+    rounding: Rounding,
+) -> Decimal {
+    let value = round_to_step(value, min, step, rounding);
+    if !max.is_zero() && value > max {
+        round_to_step(max, min, step, Rounding::Down)
+    } else if value < min {
+        min
+    } else {
+        value
+    }
+}
+
+/// Check the order against symbol filters, returning every violated rule.
+///
+/// `reference_price` (e.g. the current average price) is needed for PERCENT_PRICE
+/// filters and for the notional value of MARKET orders, those checks are skipped without it.
+/// Filters depending on the account state (MAX_NUM_ORDERS, MAX_POSITION, ...) are not checked.
+pub fn validate_order(
+    filters: &[Filter],
+    order: &NewOrderParams,
+    reference_price: Option<Decimal>,
+) -> Result<(), Vec<FilterViolation>> {
+    let is_market = order.order_type == OrderType::Market;
+    let prices: Vec<Decimal> = order.price.into_iter().chain(order.stop_price).collect();
+    let notional = match (order.quote_order_qty, order.quantity) {
+        (Some(quote_qty), _) => Some(quote_qty),
+        (None, Some(qty)) => order.price.or(reference_price).map(|price| price * qty),
+        (None, None) => None,
+    };
+
+    let mut violations = Vec::new();
+    for filter in filters {
+        match *filter {
+            Filter::PriceFilter {
+                min_price,
+                max_price,
+                tick_size,
+            } => {
+                for &price in &prices {
+                    if (!min_price.is_zero() && price < min_price)
+                        || (!max_price.is_zero() && price > max_price)
+                    {
+                        violations.push(FilterViolation::PriceOutOfRange {
+                            price,
+                            min_price,
+                            max_price,
+                        });
+                    }
+                    if !is_multiple(price - min_price, tick_size) {
+                        violations.push(FilterViolation::PriceTickSize { price, tick_size });
+                    }
+                }
+            }
+            Filter::PercentPrice {
+                multiplier_up,
+                multiplier_down,
+                ..
+            } => {
+                if let (Some(price), Some(reference)) = (order.price, reference_price) {
+                    let (min_price, max_price) =
+                        (reference * multiplier_down, reference * multiplier_up);
+                    if price < min_price || price > max_price {
+                        violations.push(FilterViolation::PercentPrice {
+                            price,
+                            min_price,
+                            max_price,
+                        });
+                    }
+                }
+            }
+            Filter::PercentPriceBySide {
+                bid_multiplier_up,
+                bid_multiplier_down,
+                ask_multiplier_up,
+                ask_multiplier_down,
+                ..
+            } => {
+                if let (Some(price), Some(reference)) = (order.price, reference_price) {
+                    let (down, up) = match order.side {
+                        OrderSide::BUY => (bid_multiplier_down, bid_multiplier_up),
+                        OrderSide::SELL => (ask_multiplier_down, ask_multiplier_up),
+                    };
+                    let (min_price, max_price) = (reference * down, reference * up);
+                    if price < min_price || price > max_price {
+                        violations.push(FilterViolation::PercentPriceBySide {
+                            side: order.side,
+                            price,
+                            min_price,
+                            max_price,
+                        });
+                    }
+                }
+            }
+            Filter::LotSize {
+                min_qty,
+                max_qty,
+                step_size,
+            } => {
+                if let Some(qty) = order.quantity {
+                    if qty < min_qty || (!max_qty.is_zero() && qty > max_qty) {
+                        violations.push(FilterViolation::LotSize {
+                            qty,
+                            min_qty,
+                            max_qty,
+                        });
+                    }
+                    if !is_multiple(qty - min_qty, step_size) {
+                        violations.push(FilterViolation::LotStepSize { qty, step_size });
+                    }
+                }
+                if let Some(iceberg_qty) = order.iceberg_qty
+                    && !is_multiple(iceberg_qty - min_qty, step_size)
+                {
+                    violations.push(FilterViolation::LotStepSize {
+                        qty: iceberg_qty,
+                        step_size,
+                    });
+                }
+            }
+            Filter::MarketLotSize {
+                min_qty,
+                max_qty,
+                step_size,
+            } => {
+                if let Some(qty) = order.quantity.filter(|_| is_market) {
+                    if qty < min_qty || (!max_qty.is_zero() && qty > max_qty) {
+                        violations.push(FilterViolation::MarketLotSize {
+                            qty,
+                            min_qty,
+                            max_qty,
+                        });
+                    }
+                    if !is_multiple(qty - min_qty, step_size) {
+                        violations.push(FilterViolation::MarketLotStepSize { qty, step_size });
+                    }
+                }
+            }
+            Filter::MinNotional {
+                min_notional,
+                apply_to_market,
+                ..
+            } => {
+                if let Some(notional) = notional.filter(|_| !is_market || apply_to_market)
+                    && notional < min_notional
+                {
+                    violations.push(FilterViolation::MinNotional {
+                        notional,
+                        min_notional,
+                    });
+                }
+            }
+            Filter::Notional {
+                min_notional,
+                apply_min_to_market,
+                max_notional,
+                apply_max_to_market,
+                ..
+            } => {
+                if let Some(notional) = notional {
+                    let below = (!is_market || apply_min_to_market) && notional < min_notional;
+                    let above = (!is_market || apply_max_to_market)
+                        && !max_notional.is_zero()
+                        && notional > max_notional;
+                    if below || above {
+                        violations.push(FilterViolation::Notional {
+                            notional,
+                            min_notional,
+                            max_notional,
+                        });
+                    }
+                }
+            }
+            Filter::IcebergParts { limit } => {
+                if let (Some(qty), Some(iceberg_qty)) = (order.quantity, order.iceberg_qty)
+                    && !iceberg_qty.is_zero()
+                {
+                    let parts = (qty / iceberg_qty).ceil();
+                    if parts > Decimal::from(limit) {
+                        violations.push(FilterViolation::IcebergParts { parts, limit });
+                    }
+                }
+            }
+            Filter::TrailingDelta {
+                min_trailing_above_delta,
+                max_trailing_above_delta,
+                min_trailing_below_delta,
+                max_trailing_below_delta,
+            } => {
+                if let Some(delta) = order.trailing_delta {
+                    use OrderType::*;
+                    // Stop price above the market: BUY stop loss and SELL take profit orders.
+                    let above = matches!(
+                        (order.side, order.order_type),
+                        (OrderSide::BUY, StopLoss | StopLossLimit)
+                            | (OrderSide::SELL, TakeProfit | TakeProfitLimit)
+                    );
+                    let (min, max) = if above {
+                        (min_trailing_above_delta, max_trailing_above_delta)
+                    } else {
+                        (min_trailing_below_delta, max_trailing_below_delta)
+                    };
+                    if delta < min || delta > max {
+                        violations.push(FilterViolation::TrailingDelta { delta, min, max });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Round value to the grid `base + n * step`, a zero step disables rounding.
+pub fn round_to_step(value: Decimal, base: Decimal, step: Decimal, rounding: Rounding) -> Decimal {
+    if step.is_zero() {
+        return value;
+    }
+    let strategy = match rounding {
+        Rounding::Down => RoundingStrategy::ToNegativeInfinity,
+        Rounding::Up => RoundingStrategy::ToPositiveInfinity,
+        Rounding::Nearest => RoundingStrategy::MidpointAwayFromZero,
+    };
+    let steps = ((value - base) / step).round_dp_with_strategy(0, strategy);
+    (base + steps * step).normalize()
+}
+
+fn is_multiple(value: Decimal, step: Decimal) -> bool {
+    step.is_zero() || (value % step).is_zero()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+
+    fn filters() -> Vec<Filter> {
+        vec![
+            Filter::PriceFilter {
+                min_price: dec!(0.01),
+                max_price: dec!(100000),
+                tick_size: dec!(0.01),
+            },
+            Filter::PercentPriceBySide {
+                bid_multiplier_up: dec!(1.2),
+                bid_multiplier_down: dec!(0.2),
+                ask_multiplier_up: dec!(5),
+                ask_multiplier_down: dec!(0.8),
+                avg_price_mins: 5,
+            },
+            Filter::LotSize {
+                min_qty: dec!(0.001),
+                max_qty: dec!(9000),
+                step_size: dec!(0.001),
+            },
+            Filter::MarketLotSize {
+                min_qty: dec!(0),
+                max_qty: dec!(100),
+                step_size: dec!(0),
+            },
+            Filter::Notional {
+                min_notional: dec!(5),
+                apply_min_to_market: true,
+                max_notional: dec!(9000000),
+                apply_max_to_market: false,
+                avg_price_mins: 5,
+            },
+            Filter::IcebergParts { limit: 10 },
+            Filter::TrailingDelta {
+                min_trailing_above_delta: 10,
+                max_trailing_above_delta: 2000,
+                min_trailing_below_delta: 10,
+                max_trailing_below_delta: 2000,
+            },
+            Filter::Unknown,
+        ]
+    }
+
+    fn order(order_type: OrderType, price: Option<Decimal>, qty: Decimal) -> NewOrderParams {
+        NewOrderParams {
+            symbol: String::from("BTCUSDT"),
+            side: OrderSide::BUY,
+            order_type,
+            time_in_force: None,
+            quantity: Some(qty),
+            quote_order_qty: None,
+            price,
+            new_client_order_id: None,
+            strategy_id: None,
+            strategy_type: None,
+            stop_price: None,
+            trailing_delta: None,
+            iceberg_qty: None,
+            new_order_resp_type: None,
+            self_trade_prevention_mode: None,
+        }
+    }
+
+    #[test]
+    fn valid_orders() {
+        let filters = filters();
+        let limit = order(OrderType::Limit, Some(dec!(100.01)), dec!(0.5));
+        assert_eq!(validate_order(&filters, &limit, Some(dec!(100))), Ok(()));
+
+        let market = order(OrderType::Market, None, dec!(0.5));
+        assert_eq!(validate_order(&filters, &market, Some(dec!(100))), Ok(()));
+        // Notional of MARKET orders is unknown without a reference price.
+        let market = order(OrderType::Market, None, dec!(0.001));
+        assert_eq!(validate_order(&filters, &market, None), Ok(()));
+
+        // A zero max notional disables the upper bound.
+        let filters = [Filter::Notional {
+            min_notional: dec!(5),
+            apply_min_to_market: true,
+            max_notional: dec!(0),
+            apply_max_to_market: true,
+            avg_price_mins: 5,
+        }];
+        let limit = order(OrderType::Limit, Some(dec!(100000)), dec!(9000));
+        assert_eq!(validate_order(&filters, &limit, None), Ok(()));
+    }
+
+    #[test]
+    fn all_violations() {
+        let filters = filters();
+        let mut limit = order(OrderType::Limit, Some(dec!(130.005)), dec!(0.0105));
+        limit.iceberg_qty = Some(dec!(0.001));
+        let violations = validate_order(&filters, &limit, Some(dec!(100))).unwrap_err();
+        assert_eq!(
+            violations,
+            vec![
+                FilterViolation::PriceTickSize {
+                    price: dec!(130.005),
+                    tick_size: dec!(0.01),
+                },
+                FilterViolation::PercentPriceBySide {
+                    side: OrderSide::BUY,
+                    price: dec!(130.005),
+                    min_price: dec!(20),
+                    max_price: dec!(120),
+                },
+                FilterViolation::LotStepSize {
+                    qty: dec!(0.0105),
+                    step_size: dec!(0.001),
+                },
+                FilterViolation::Notional {
+                    notional: dec!(1.3650525),
+                    min_notional: dec!(5),
+                    max_notional: dec!(9000000),
+                },
+                FilterViolation::IcebergParts {
+                    parts: dec!(11),
+                    limit: 10,
+                },
+            ]
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "Filter failure: PRICE_FILTER: price 130.005 is not a multiple of 0.01"
+        );
+
+        let mut stop = order(OrderType::StopLoss, None, dec!(200));
+        stop.side = OrderSide::SELL;
+        stop.trailing_delta = Some(5);
+        let violations = validate_order(&filters, &stop, Some(dec!(100))).unwrap_err();
+        let names: Vec<_> = violations.iter().map(FilterViolation::filter).collect();
+        assert_eq!(names, ["TRAILING_DELTA"]);
+
+        let market = order(OrderType::Market, None, dec!(200));
+        let violations = validate_order(&filters, &market, Some(dec!(100))).unwrap_err();
+        let names: Vec<_> = violations.iter().map(FilterViolation::filter).collect();
+        assert_eq!(names, ["MARKET_LOT_SIZE"]);
+    }
+
+    #[test]
+    fn rounding() {
+        let step = dec!(0.01);
+        let base = dec!(0.01);
+        assert_eq!(
+            round_to_step(dec!(1.005), base, step, Rounding::Down),
+            dec!(1)
+        );
+        assert_eq!(
+            round_to_step(dec!(1.001), base, step, Rounding::Up),
+            dec!(1.01)
+        );
+        assert_eq!(
+            round_to_step(dec!(1.005), base, step, Rounding::Nearest),
+            dec!(1.01)
+        );
+        assert_eq!(
+            round_to_step(dec!(-1.005), dec!(0), step, Rounding::Down),
+            dec!(-1.01)
+        );
+        assert_eq!(
+            round_to_step(dec!(1.2345), base, dec!(0), Rounding::Up),
+            dec!(1.2345)
+        );
+
+        // Rounded values pass the filters they were rounded to.
+        let filters = filters();
+        let price = round_to_step(dec!(100.0149), dec!(0.01), dec!(0.01), Rounding::Nearest);
+        let qty = round_to_step(dec!(0.12345), dec!(0.001), dec!(0.001), Rounding::Down);
+        assert_eq!((price, qty), (dec!(100.01), dec!(0.123)));
+        let limit = order(OrderType::Limit, Some(price), qty);
+        assert_eq!(validate_order(&filters, &limit, None), Ok(()));
+    }
+
+    #[test]
+    fn rounding_within_filter_range() {
+        let filters = filters();
+        assert_eq!(
+            round_price(&filters, dec!(100000.005), Rounding::Up),
+            dec!(100000)
+        );
+        assert_eq!(
+            round_price(&filters, dec!(0.001), Rounding::Down),
+            dec!(0.01)
+        );
+        assert_eq!(
+            round_qty(&filters, dec!(0.0001), OrderType::Limit, Rounding::Down),
+            dec!(0.001)
+        );
+        assert_eq!(
+            round_qty(&filters, dec!(9000.0001), OrderType::Limit, Rounding::Up),
+            dec!(9000)
+        );
+        // MARKET_LOT_SIZE applies to MARKET orders only.
+        assert_eq!(
+            round_qty(
+                &filters,
+                dec!(200.0004),
+                OrderType::Market,
+                Rounding::Nearest
+            ),
+            dec!(100)
+        );
+        let market = order(OrderType::Market, None, dec!(100));
+        assert_eq!(validate_order(&filters, &market, Some(dec!(100))), Ok(()));
+        assert_eq!(
+            round_qty(
+                &filters,
+                dec!(200.0004),
+                OrderType::Limit,
+                Rounding::Nearest
+            ),
+            dec!(200)
+        );
+    }
+
+    #[test]
+    fn violation_as_error() {
+        let violation = FilterViolation::LotStepSize {
+            qty: dec!(0.0105),
+            step_size: dec!(0.001),
+        };
+        let error = Error::from(violation);
+        assert_eq!(error.code(), Some(ErrorCode::InvalidMessage));
+        assert!(error.to_string().contains("Filter failure: LOT_SIZE"));
+    }
+}
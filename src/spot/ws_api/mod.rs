@@ -0,0 +1,376 @@
+//! WebSocket API: the REST endpoints as request/response over one connection.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), binance::spot::Error> {
+//! use binance::spot::{GetKlineListParams, KlineInterval, ws_api::{WsApiClient, WsApiConfig}};
+//!
+//! let client = WsApiClient::connect(WsApiConfig::default()).await?;
+//! let params = GetKlineListParams {
+//!     symbol: String::from("BTCUSDT"),
+//!     interval: KlineInterval::Minute1,
+//!     start_time: None,
+//!     end_time: None,
+//!     time_zone: None,
+//!     limit: Some(10),
+//! };
+//! let response = client.get_kline_list(params).await?;
+//! println!("{:?} {:?}", response.result, response.rate_limits);
+//! # Ok(())
+//! # }
+//! ```
+
+mod methods;
+#[cfg(test)]
+pub(crate) mod test_server;
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use futures_util::{
+    SinkExt, StreamExt,
+    stream::{SplitSink, SplitStream},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value, json};
+use tokio::{net::TcpStream, sync::oneshot, task::JoinHandle};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message};
+
+use crate::spot::{
+    ApiError, BASE_URL_WS_API, Error, RateLimitInterval, RateLimiter, SecurityType, Timestamp,
+    crypto::{SensitiveString, Signer},
+    timestamp,
+};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+type Pending = Arc<Mutex<Option<HashMap<u64, oneshot::Sender<RawResponse>>>>>;
+
+pub struct WsApiConfig {
+    pub base_url: String,
+    pub api_key: Option<SensitiveString>,
+    /// HMAC secret key or PKCS#8 PEM encoded RSA/Ed25519 private key.
+    /// `session.logon` requires an Ed25519 key.
+    pub api_secret: Option<SensitiveString>,
+    pub recv_window: Option<u64>,
+    /// How long to wait for the response to a request.
+    pub request_timeout: Duration,
+}
+
+impl Default for WsApiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: BASE_URL_WS_API.to_string(),
+            api_key: None,
+            api_secret: None,
+            recv_window: None,
+            request_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Result with the rate limit usage after the request.
+#[derive(Debug)]
+pub struct WsResponse<T> {
+    pub result: T,
+    pub rate_limits: Vec<RateLimitCount>,
+}
+
+/// Rate limit with its current usage.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitCount {
+    pub rate_limit_type: RateLimiter,
+    pub interval: RateLimitInterval,
+    pub interval_num: u64,
+    pub limit: u64,
+    pub count: u64,
+}
+
+/// Authentication state of the connection.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatus {
+    /// API key the session is logged on with, `None` before `session.logon`.
+    pub api_key: Option<String>,
+    pub authorized_since: Option<Timestamp>,
+    pub connected_since: Timestamp,
+    pub return_rate_limits: bool,
+    pub server_time: Timestamp,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawResponse {
+    id: Option<u64>,
+    status: u16,
+    #[serde(default)]
+    result: Value,
+    error: Option<ApiError>,
+    #[serde(default)]
+    rate_limits: Vec<RateLimitCount>,
+}
+
+/// WebSocket API client. Requests can be sent concurrently, responses are matched by request id.
+///
+/// Connections are closed by Binance after 24 hours.
+pub struct WsApiClient {
+    cfg: WsApiConfig,
+    signer: Option<Signer>,
+    sink: tokio::sync::Mutex<SplitSink<Socket, Message>>,
+    pending: Pending,
+    next_id: AtomicU64,
+    logged_on: AtomicBool,
+    rate_limits: Mutex<Vec<RateLimitCount>>,
+    reader: JoinHandle<()>,
+}
+
+impl WsApiClient {
+    pub async fn connect(cfg: WsApiConfig) -> Result<Self, Error> {
+        let signer = cfg.api_secret.as_ref().map(Signer::new).transpose()?;
+        let (socket, _) = connect_async(cfg.base_url.as_str()).await?;
+        let (sink, stream) = socket.split();
+        let pending: Pending = Arc::new(Mutex::new(Some(HashMap::new())));
+        let reader = tokio::spawn(read(stream, Arc::clone(&pending)));
+
+        Ok(Self {
+            cfg,
+            signer,
+            sink: tokio::sync::Mutex::new(sink),
+            pending,
+            next_id: AtomicU64::new(1),
+            logged_on: AtomicBool::new(false),
+            rate_limits: Mutex::new(Vec::new()),
+            reader,
+        })
+    }
+
+    /// Rate limit usage reported with the last response.
+    pub fn rate_limits(&self) -> Vec<RateLimitCount> {
+        self.rate_limits
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Whether the connection is authenticated with `session.logon`.
+    pub fn is_logged_on(&self) -> bool {
+        self.logged_on.load(Ordering::Relaxed)
+    }
+
+    pub async fn close(&self) -> Result<(), Error> {
+        self.sink.lock().await.close().await?;
+        Ok(())
+    }
+
+    /// Send request and wait for its response.
+    ///
+    /// USER_STREAM requests get the `apiKey` parameter. TRADE and USER_DATA requests
+    /// additionally get `timestamp`, `recvWindow` and `signature`, or only `timestamp`
+    /// and `recvWindow` once the session is logged on.
+    pub(crate) async fn request<P, T>(
+        &self,
+        method: &str,
+        security: SecurityType,
+        params: &P,
+    ) -> Result<WsResponse<T>, Error>
+    where
+        P: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let mut params = to_params(params)?;
+        match security {
+            SecurityType::None => {}
+            SecurityType::UserStream => {
+                params.insert(String::from("apiKey"), self.api_key()?.into());
+            }
+            SecurityType::Trade | SecurityType::UserData => {
+                if self.is_logged_on() {
+                    self.add_timestamp(&mut params);
+                } else {
+                    self.sign(&mut params)?;
+                }
+            }
+        }
+        self.send(method, params).await
+    }
+
+    async fn send<T>(
+        &self,
+        method: &str,
+        params: Map<String, Value>,
+    ) -> Result<WsResponse<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut request = json!({ "id": id, "method": method });
+        if !params.is_empty() {
+            request["params"] = Value::Object(params);
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        self.pending_requests(|pending| pending.insert(id, sender))
+            .ok_or("WebSocket API connection closed")?;
+        let sent = self
+            .sink
+            .lock()
+            .await
+            .send(Message::text(request.to_string()))
+            .await;
+        if let Err(error) = sent {
+            self.pending_requests(|pending| pending.remove(&id));
+            return Err(error.into());
+        }
+
+        let response = match tokio::time::timeout(self.cfg.request_timeout, receiver).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(_)) => return Err(Error::from("WebSocket API connection closed")),
+            Err(_) => {
+                self.pending_requests(|pending| pending.remove(&id));
+                return Err(Error::Msg(format!("{method} request timed out")));
+            }
+        };
+
+        if !response.rate_limits.is_empty() {
+            *self
+                .rate_limits
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = response.rate_limits.clone();
+        }
+        if let Some(ApiError { code, msg }) = response.error {
+            let status = response.status;
+            return Err(Error::Api { status, code, msg });
+        }
+        let result = serde_path_to_error::deserialize(response.result)?;
+        Ok(WsResponse {
+            result,
+            rate_limits: response.rate_limits,
+        })
+    }
+
+    fn pending_requests<R>(
+        &self,
+        f: impl FnOnce(&mut HashMap<u64, oneshot::Sender<RawResponse>>) -> R,
+    ) -> Option<R> {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        pending.as_mut().map(f)
+    }
+
+    fn api_key(&self) -> Result<&str, Error> {
+        let api_key = self
+            .cfg
+            .api_key
+            .as_ref()
+            .ok_or("api_key is required for this method")?;
+        Ok(api_key.expose())
+    }
+
+    fn add_timestamp(&self, params: &mut Map<String, Value>) {
+        params.insert(String::from("timestamp"), timestamp().into());
+        if let Some(recv_window) = self.cfg.recv_window {
+            params.insert(String::from("recvWindow"), recv_window.into());
+        }
+    }
+
+    /// Add `apiKey`, `timestamp`, `recvWindow` and `signature` of all parameters sorted by name.
+    fn sign(&self, params: &mut Map<String, Value>) -> Result<(), Error> {
+        let signer = self
+            .signer
+            .as_ref()
+            .ok_or("api_secret is required for this method")?;
+        params.insert(String::from("apiKey"), self.api_key()?.into());
+        self.add_timestamp(params);
+
+        let signature = signer.sign(&payload(params));
+        params.insert(String::from("signature"), signature.into());
+        Ok(())
+    }
+}
+
+impl Drop for WsApiClient {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// Route responses to waiting requests, failing all of them once the connection is closed.
+async fn read(mut stream: SplitStream<Socket>, pending: Pending) {
+    while let Some(Ok(message)) = stream.next().await {
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let Ok(response) = serde_json::from_str::<RawResponse>(&text) else {
+            continue;
+        };
+        let sender = response.id.and_then(|id| {
+            let mut pending = pending.lock().unwrap_or_else(PoisonError::into_inner);
+            pending.as_mut()?.remove(&id)
+        });
+        if let Some(sender) = sender {
+            let _ = sender.send(response);
+        }
+    }
+    // Dropping the senders fails the waiting requests.
+    pending
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+}
+
+/// Serialize parameters to a JSON object without unset (null) values.
+fn to_params<P>(params: &P) -> Result<Map<String, Value>, Error>
+where
+    P: Serialize + ?Sized,
+{
+    match serde_json::to_value(params)? {
+        Value::Object(mut params) => {
+            params.retain(|_, value| !value.is_null());
+            Ok(params)
+        }
+        Value::Null => Ok(Map::new()),
+        _ => Err(Error::from("parameters must be a struct")),
+    }
+}
+
+/// Signature payload: `name=value` pairs sorted by name and joined with `&`.
+fn payload(params: &Map<String, Value>) -> String {
+    let mut pairs: Vec<(&String, &Value)> = params.iter().collect();
+    pairs.sort_by_key(|(name, _)| *name);
+    pairs
+        .into_iter()
+        .map(|(name, value)| match value {
+            Value::String(value) => format!("{name}={value}"),
+            value => format!("{name}={value}"),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_payload() {
+        let params = to_params(&json!({
+            "symbol": "BTCUSDT",
+            "quantity": "0.01",
+            "timestamp": 1499827319559u64,
+            "newOrderRespType": null,
+            "apiKey": "key",
+        }))
+        .unwrap();
+
+        assert_eq!(
+            payload(&params),
+            "apiKey=key&quantity=0.01&symbol=BTCUSDT&timestamp=1499827319559"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Local WebSocket API server shared by the tests of [`WsApiClient`](super::WsApiClient).
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+use crate::spot::crypto::{SensitiveString, Signer};
+
+use super::payload;
+
+/// Answers requests like the WebSocket API, `depth` and `ping` are answered
+/// together in reverse order.
+pub(crate) async fn server(secret: SensitiveString) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let signer = Signer::new(&secret).unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut socket = accept_async(stream).await.unwrap();
+        let mut held = Vec::new();
+        let mut logged_on = false;
+        let rate_limits = json!([{"rateLimitType":"REQUEST_WEIGHT","interval":"MINUTE","intervalNum":1,"limit":6000,"count":7}]);
+        while let Some(Ok(Message::Text(text))) = socket.next().await {
+            let request: Value = serde_json::from_str(&text).unwrap();
+            let id = request["id"].clone();
+            let mut params = request["params"].as_object().cloned().unwrap_or_default();
+            let signature = params.remove("signature");
+            let signed = signature
+                .as_ref()
+                .is_some_and(|signature| signature == &signer.sign(&payload(&params)));
+
+            let result = match request["method"].as_str().unwrap() {
+                "time" => json!({"serverTime": 1499827319559u64}),
+                "avgPrice" => {
+                    json!({"mins": 5, "price": "9.35751834", "closeTime": 1694061154503u64})
+                }
+                "exchangeInfo" if params["symbols"].is_array() => {
+                    json!({"timezone": "UTC", "serverTime": 1, "rateLimits": [], "symbols": []})
+                }
+                "ping" | "depth" => {
+                    held.push(request);
+                    if held.len() < 2 {
+                        continue;
+                    }
+                    for request in held.drain(..).rev() {
+                        let result = match request["method"].as_str().unwrap() {
+                            "ping" => json!({}),
+                            _ => {
+                                json!({"lastUpdateId": 1, "bids": [["1.0", "2.0"]], "asks": []})
+                            }
+                        };
+                        let response =
+                            json!({"id": request["id"], "status": 200, "result": result});
+                        socket
+                            .send(Message::text(response.to_string()))
+                            .await
+                            .unwrap();
+                    }
+                    continue;
+                }
+                "order.place" if signed && params["apiKey"] == "api-key" => json!({
+                    "symbol": params["symbol"],
+                    "orderId": 28,
+                    "orderListId": -1,
+                    "clientOrderId": "6gCrw2kRUAF9CvJDGP16IP",
+                    "transactTime": 1507725176595u64
+                }),
+                "session.logon" if signed => {
+                    logged_on = true;
+                    json!({"apiKey": params["apiKey"], "authorizedSince": 1, "connectedSince": 1, "returnRateLimits": true, "serverTime": 1})
+                }
+                "account.rateLimits.orders"
+                    if logged_on && signature.is_none() && params.contains_key("timestamp") =>
+                {
+                    json!([])
+                }
+                _ => {
+                    let error =
+                        json!({"code": -1022, "msg": "Signature for this request is not valid."});
+                    let response =
+                        json!({"id": id, "status": 400, "error": error, "rateLimits": rate_limits});
+                    socket
+                        .send(Message::text(response.to_string()))
+                        .await
+                        .unwrap();
+                    continue;
+                }
+            };
+            let response =
+                json!({"id": id, "status": 200, "result": result, "rateLimits": rate_limits});
+            socket
+                .send(Message::text(response.to_string()))
+                .await
+                .unwrap();
+        }
+    });
+    format!("ws://{addr}")
+}